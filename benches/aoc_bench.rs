@@ -1,6 +1,6 @@
+use aoc::utils::read_lines::read_input;
 use aoc::{year2024, year2025};
 use criterion::{Criterion, criterion_group, criterion_main};
-use std::fs;
 use std::hint::black_box;
 
 fn benchmark_solutions(c: &mut Criterion) {
@@ -9,10 +9,8 @@ fn benchmark_solutions(c: &mut Criterion) {
     all_solutions.extend(year2025::get_solutions());
 
     for sol in all_solutions {
-        let path = format!("input/year{}/day{:02}.txt", sol.year, sol.day);
-
         // Only bench if the input file exists
-        if let Ok(data) = fs::read_to_string(&path) {
+        if let Ok(data) = read_input(sol.year, sol.day) {
             let group_name = format!("Year {} Day {:02}", sol.year, sol.day);
 
             c.bench_function(&group_name, |b| {
@@ -6,7 +6,8 @@ extern crate core;
 pub struct SolutionStruct {
     pub year: u32,
     pub day: u32,
-    pub wrapper: fn(&str) -> (String, String),
+    /// Runs the day on the given input, returning both answers or the error message.
+    pub wrapper: fn(&str) -> Result<(String, String), String>,
 }
 
 pub use crate::SolutionStruct as Solution;
@@ -28,7 +29,7 @@ macro_rules! library {
     }
 }
 
-library!(utils "Utility functions" grid, disjointset, read_lines, simplex, interval_tree, point);
+library!(utils "Utility functions" grid, disjointset, read_lines, simplex, interval_tree, point, numbers, iter, combinatorics, graph, pathfinding, linalg, bitgrid, spatial);
 
 // --- 4. The Registration Macro ---
 // This is exported so it can be used inside your year/mod.rs files.
@@ -51,8 +52,8 @@ macro_rules! register_year {
                         wrapper: |data| {
                             // 'self' refers to the module where the macro is called (e.g., year2025)
                             match self::$day_mod::main(data) {
-                                Ok((p1, p2)) => (p1.to_string(), p2.to_string()),
-                                Err(e) => (format!("Error: {}", e), String::from("???")),
+                                Ok((p1, p2)) => Ok((p1.to_string(), p2.to_string())),
+                                Err(e) => Err(e.to_string()),
                             }
                         }
                     }
@@ -1,37 +1,63 @@
-use clap::Parser;
+use anyhow::{bail, Context, Result};
+use clap::{Parser, ValueEnum};
 use colored::Colorize;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
-use std::fs;
-use std::path::Path;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
+use aoc::utils::read_lines::read_input_from;
 use aoc::{year2024, year2025, Solution};
 
 #[derive(Parser, Debug)]
 #[command(version, about = "🎄 Advent of Code Dashboard")]
 struct Args {
+    /// Run every registered day; the same as passing no --year or --day
+    #[arg(long, conflicts_with_all = ["year", "day"])]
+    all: bool,
     #[arg(short, long)]
     year: Option<u32>,
     #[arg(short, long)]
     day: Option<u32>,
+    /// Read the puzzle input from stdin for the selected --year and --day
+    #[arg(long)]
+    stdin: bool,
+    /// Highlight days whose runtime exceeds this many milliseconds
+    #[arg(long, value_name = "MS")]
+    slow_threshold: Option<u64>,
+    /// Directory containing the yearYYYY/dayDD.txt puzzle inputs
+    #[arg(long, default_value = "input")]
+    input_dir: PathBuf,
+    /// Unit for reported runtimes; `auto` picks µs, ms or s by magnitude
+    #[arg(long, value_enum, default_value_t = TimeFormat::Auto)]
+    time_format: TimeFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum TimeFormat {
+    Ms,
+    Us,
+    Ns,
+    Auto,
 }
 
 fn main() {
     let args = Args::parse();
 
+    if args.stdin {
+        if let Err(e) = run_stdin(args.year, args.day, io::stdin(), &mut io::stdout()) {
+            eprintln!("{} {}", "error:".red().bold(), e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // 1. Pretty Header
     println!("\n{}", " ❄  ADVENT OF CODE RUNNER  ❄ ".bold().white().on_blue());
     println!("{}", "=".repeat(32).blue());
 
-    let all_solutions: Vec<Solution> = [
-        year2024::get_solutions(),
-        year2025::get_solutions(),
-    ]
-        .into_iter()
-        .flatten()
-        .filter(|s| args.year.is_none_or(|y| y == s.year))
-        .filter(|s| args.day.is_none_or(|d| d == s.day))
-        .collect();
+    let all_solutions = select_solutions(&args);
+    let days_run = all_solutions.len();
 
     if all_solutions.is_empty() {
         println!("{}", "  No solutions matched your filters.".dimmed());
@@ -53,6 +79,7 @@ fn main() {
     let mut total_stars = 0;
     let mut total_duration = Duration::ZERO;
     let mut current_year = 0;
+    let mut slow_days = Vec::new();
 
     for solution in all_solutions {
         // Grouping by Year
@@ -63,7 +90,10 @@ fn main() {
 
         pb.set_message(format!("Day {:02}", solution.day));
 
-        let (stars, duration) = run_solution(&solution, &pb);
+        let (stars, duration) = run_solution(&solution, &args.input_dir, args.time_format, &pb);
+        if is_slow(duration, args.slow_threshold) {
+            slow_days.push((solution.year, solution.day, duration));
+        }
 
         total_stars += stars;
         total_duration += duration;
@@ -76,32 +106,115 @@ fn main() {
     println!("\n{}", "📊 FINAL STATS".bold().magenta());
     println!("{}", "─".repeat(20).magenta());
     println!("⭐ Stars:    {}", total_stars.to_string().yellow().bold());
-    println!("🕓 Time:     {}", format_duration(total_duration));
+    println!("🕓 Time:     {}", format_duration(total_duration, args.time_format));
+    if runs_everything(&args) {
+        println!("📆 Days:     {}", days_run.to_string().cyan().bold());
+    }
     println!("{}\n", "─".repeat(20).magenta());
+
+    if let Some(threshold) = args.slow_threshold
+        && !slow_days.is_empty()
+    {
+        for (year, day, duration) in &slow_days {
+            println!(
+                "  {} {} Day {:02}: {}",
+                "🐢".red(),
+                year,
+                day,
+                render_elapsed(*duration, args.time_format).red().bold()
+            );
+        }
+        let noun = if slow_days.len() == 1 { "day" } else { "days" };
+        println!(
+            "{}\n",
+            format!("{} {noun} exceeded {} ms", slow_days.len(), threshold).red()
+        );
+    }
 }
 
-fn run_solution(sol: &Solution, pb: &ProgressBar) -> (u32, Duration) {
-    let path_str = format!("input/year{}/day{:02}.txt", sol.year, sol.day);
-    let Ok(data) = fs::read_to_string(Path::new(&path_str)) else {
+/// Every solution registered across all years, in year then day order.
+fn registered_solutions() -> impl Iterator<Item = Solution> {
+    [year2024::get_solutions(), year2025::get_solutions()]
+        .into_iter()
+        .flatten()
+}
+
+/// Whether `args` selects every registered day, either via `--all` or by omitting filters.
+fn runs_everything(args: &Args) -> bool {
+    args.all || (args.year.is_none() && args.day.is_none())
+}
+
+/// The registered solutions matching the `--year`/`--day` filters.
+fn select_solutions(args: &Args) -> Vec<Solution> {
+    registered_solutions()
+        .filter(|s| args.year.is_none_or(|y| y == s.year))
+        .filter(|s| args.day.is_none_or(|d| d == s.day))
+        .collect()
+}
+
+/// Whether a run taking `elapsed` is over the optional `--slow-threshold` in milliseconds.
+fn is_slow(elapsed: Duration, threshold_ms: Option<u64>) -> bool {
+    threshold_ms.is_some_and(|ms| elapsed > Duration::from_millis(ms))
+}
+
+/// Runs `sol` on `data`, returning both answers and the time taken.
+fn time_solution(sol: &Solution, data: &str) -> (Result<(String, String), String>, Duration) {
+    let start = Instant::now();
+    let answers = (sol.wrapper)(data);
+    (answers, start.elapsed())
+}
+
+/// Runs a single day on input read from `input`, printing both answers to `out`.
+fn run_stdin(
+    year: Option<u32>,
+    day: Option<u32>,
+    mut input: impl Read,
+    out: &mut impl Write,
+) -> Result<()> {
+    let (Some(year), Some(day)) = (year, day) else {
+        bail!("--stdin requires both --year and --day");
+    };
+    let Some(sol) = registered_solutions().find(|s| s.year == year && s.day == day) else {
+        bail!("No solution registered for {} day {:02}", year, day);
+    };
+
+    let mut data = String::new();
+    input
+        .read_to_string(&mut data)
+        .context("Failed to read input from stdin")?;
+
+    let (p1, p2) = (sol.wrapper)(&data).map_err(anyhow::Error::msg)?;
+    writeln!(out, "Part 1: {}", p1)?;
+    writeln!(out, "Part 2: {}", p2)?;
+    Ok(())
+}
+
+fn run_solution(
+    sol: &Solution,
+    input_dir: &Path,
+    time_format: TimeFormat,
+    pb: &ProgressBar,
+) -> (u32, Duration) {
+    let Ok(data) = read_input_from(input_dir, sol.year, sol.day) else {
         pb.println(format!("  {} Day {:02}: {}", "⚠".red(), sol.day, "Input missing".dimmed()));
         return (0, Duration::ZERO);
     };
 
-    let start = Instant::now();
-    let (p1, p2) = (sol.wrapper)(&data);
-    let elapsed = start.elapsed();
+    let (answers, elapsed) = time_solution(sol, &data);
+    let (stars, p1, p2) = score(answers);
 
     // 4. Color-coded timing (Heatmap style)
+    let time_text = render_elapsed(elapsed, time_format);
     let time_color = if elapsed.as_millis() < 100 {
-        elapsed.as_millis().to_string().green()
+        time_text.green()
     } else if elapsed.as_millis() < 1000 {
-        elapsed.as_millis().to_string().yellow()
+        time_text.yellow()
     } else {
-        elapsed.as_millis().to_string().red()
+        time_text.red()
     };
 
     pb.println(format!(
-        "  {} Day {:02} {} {} ms\n    {} {}\n    {} {}",
+        "  {} Day {:02} {} {}\n    {} {}\n    {} {}",
         "✨".blue(),
         sol.day,
         "─".dimmed(),
@@ -110,13 +223,141 @@ fn run_solution(sol: &Solution, pb: &ProgressBar) -> (u32, Duration) {
         "▪ Part 2:".dimmed(), p2.white().bold(),
     ));
 
-    (2, elapsed)
+    (stars, elapsed)
 }
 
-fn format_duration(d: Duration) -> String {
+/// Stars earned by a run, one per computed part, along with the text to show for each part.
+fn score(answers: Result<(String, String), String>) -> (u32, String, String) {
+    match answers {
+        Ok((p1, p2)) => (2, p1, p2),
+        Err(e) => (0, format!("Error: {}", e), String::from("???")),
+    }
+}
+
+fn format_duration(d: Duration, time_format: TimeFormat) -> String {
+    let text = render_elapsed(d, time_format);
     if d.as_secs() > 0 {
-        format!("{:.2}s", d.as_secs_f32()).red().bold().to_string()
+        text.red().bold().to_string()
     } else {
-        format!("{}ms", d.as_millis()).cyan().bold().to_string()
+        text.cyan().bold().to_string()
+    }
+}
+
+/// Renders `d` in the unit chosen by `time_format`, without any colouring.
+fn render_elapsed(d: Duration, time_format: TimeFormat) -> String {
+    match time_format {
+        TimeFormat::Ms => format!("{} ms", d.as_millis()),
+        TimeFormat::Us => format!("{} µs", d.as_micros()),
+        TimeFormat::Ns => format!("{} ns", d.as_nanos()),
+        TimeFormat::Auto if d < Duration::from_millis(1) => format!("{} µs", d.as_micros()),
+        TimeFormat::Auto if d < Duration::from_secs(1) => format!("{} ms", d.as_millis()),
+        TimeFormat::Auto => format!("{:.2} s", d.as_secs_f32()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_stdin_prints_answers() {
+        let example = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+        let mut out = Vec::new();
+        run_stdin(Some(2025), Some(1), example.as_bytes(), &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "Part 1: 3\nPart 2: 6\n");
+    }
+
+    #[test]
+    fn test_slow_threshold() {
+        let slow = Solution {
+            year: 2000,
+            day: 1,
+            wrapper: |_| {
+                std::thread::sleep(Duration::from_millis(30));
+                Ok((String::new(), String::new()))
+            },
+        };
+        let fast = Solution {
+            year: 2000,
+            day: 2,
+            wrapper: |_| Ok((String::new(), String::new())),
+        };
+
+        let (_, slow_elapsed) = time_solution(&slow, "");
+        let (_, fast_elapsed) = time_solution(&fast, "");
+        assert!(is_slow(slow_elapsed, Some(10)));
+        assert!(!is_slow(fast_elapsed, Some(10)));
+        assert!(!is_slow(slow_elapsed, None));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_stars_only_for_computed_answers() {
+        let working = Solution {
+            year: 2000,
+            day: 1,
+            wrapper: |_| Ok((String::from("1"), String::from("2"))),
+        };
+        let failing = Solution {
+            year: 2000,
+            day: 2,
+            wrapper: |_| Err(String::from("bad input")),
+        };
+
+        let (stars, p1, p2) = score((working.wrapper)(""));
+        assert_eq!((stars, p1.as_str(), p2.as_str()), (2, "1", "2"));
+
+        let (stars, p1, _) = score((failing.wrapper)(""));
+        assert_eq!(stars, 0);
+        assert_eq!(p1, "Error: bad input");
+    }
+
+    #[test]
+    fn test_run_stdin_requires_year_and_day() {
+        let mut out = Vec::new();
+        assert!(run_stdin(Some(2025), None, "".as_bytes(), &mut out).is_err());
+        assert!(run_stdin(None, Some(1), "".as_bytes(), &mut out).is_err());
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_input_dir_flag() {
+        let default = Args::try_parse_from(["aoc"]).unwrap();
+        assert_eq!(default.input_dir, Path::new("input"));
+
+        let custom = Args::try_parse_from(["aoc", "--input-dir", "inputs/alice"]).unwrap();
+        assert_eq!(
+            aoc::utils::read_lines::input_path_in(&custom.input_dir, 2025, 1),
+            Path::new("inputs/alice/year2025/day01.txt")
+        );
+    }
+
+    #[test]
+    fn test_all_runs_every_registered_day() {
+        let all = Args::try_parse_from(["aoc", "--all"]).unwrap();
+        assert!(runs_everything(&all));
+        assert_eq!(select_solutions(&all).len(), registered_solutions().count());
+        assert!(select_solutions(&all).iter().any(|s| s.year == 2024));
+        assert!(select_solutions(&all).iter().any(|s| s.year == 2025));
+
+        let filtered = Args::try_parse_from(["aoc", "--year", "2025"]).unwrap();
+        assert!(!runs_everything(&filtered));
+        assert!(select_solutions(&filtered).iter().all(|s| s.year == 2025));
+
+        assert!(Args::try_parse_from(["aoc", "--all", "--day", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_time_format() {
+        let fast = Duration::from_micros(250);
+        assert_eq!(render_elapsed(fast, TimeFormat::Auto), "250 µs");
+        assert_eq!(render_elapsed(fast, TimeFormat::Ms), "0 ms");
+        assert_eq!(render_elapsed(fast, TimeFormat::Ns), "250000 ns");
+        assert_eq!(render_elapsed(Duration::from_millis(42), TimeFormat::Auto), "42 ms");
+        assert_eq!(render_elapsed(Duration::from_millis(1500), TimeFormat::Auto), "1.50 s");
+
+        let default = Args::try_parse_from(["aoc"]).unwrap();
+        assert_eq!(default.time_format, TimeFormat::Auto);
+        let us = Args::try_parse_from(["aoc", "--time-format", "us"]).unwrap();
+        assert_eq!(us.time_format, TimeFormat::Us);
+    }
+}
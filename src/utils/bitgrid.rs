@@ -0,0 +1,139 @@
+//! A bit-packed boolean grid.
+//!
+//! [`BitGrid`] stores one bit per cell in `u64` words, an eighth of the memory of a
+//! [`Grid<bool>`], and is indexed by [`Point`] in the same row-major layout.
+
+use crate::utils::grid::Grid;
+use crate::utils::point::Point;
+use std::ops::Index;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    pub width: usize,
+    pub height: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates a `width` x `height` grid with every cell `false`.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            words: vec![0; (width * height).div_ceil(WORD_BITS)],
+        }
+    }
+
+    #[inline]
+    pub fn in_bounds(&self, p: Point) -> bool {
+        p.x >= 0 && p.x < self.width as i32 && p.y >= 0 && p.y < self.height as i32
+    }
+
+    /// Returns the cell at `p`, or `None` if `p` is out of bounds.
+    #[inline]
+    pub fn get(&self, p: Point) -> Option<bool> {
+        self.in_bounds(p).then(|| self.bit(self.to_idx(p)))
+    }
+
+    /// Sets the cell at `p` to `value`.
+    ///
+    /// # Panics
+    /// If `p` is out of bounds.
+    pub fn set(&mut self, p: Point, value: bool) {
+        assert!(
+            self.in_bounds(p),
+            "{p:?} is outside a {}x{} grid",
+            self.width,
+            self.height
+        );
+        let idx = self.to_idx(p);
+        let mask = 1 << (idx % WORD_BITS);
+        if value {
+            self.words[idx / WORD_BITS] |= mask;
+        } else {
+            self.words[idx / WORD_BITS] &= !mask;
+        }
+    }
+
+    /// Number of `true` cells.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[inline]
+    fn to_idx(&self, p: Point) -> usize {
+        (p.y as usize * self.width) + p.x as usize
+    }
+
+    #[inline]
+    fn bit(&self, idx: usize) -> bool {
+        self.words[idx / WORD_BITS] >> (idx % WORD_BITS) & 1 == 1
+    }
+}
+
+impl Index<Point> for BitGrid {
+    type Output = bool;
+    fn index(&self, p: Point) -> &Self::Output {
+        if self.get(p).expect("Point out of bounds") {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bits = Self::new(grid.width, grid.height);
+        for (idx, _) in grid.iter().enumerate().filter(|&(_, &b)| b) {
+            bits.words[idx / WORD_BITS] |= 1 << (idx % WORD_BITS);
+        }
+        bits
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bits: &BitGrid) -> Self {
+        let g = (0..bits.width * bits.height)
+            .map(|idx| bits.bit(idx))
+            .collect();
+        Grid::from_vals(g, bits.width, bits.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_round_trip() {
+        // 9x8 = 72 cells spans two words
+        let chars = Grid::from_str(
+            "#..#....#\n.#.......\n..#....##\n.........\n#########\n.........\n#.#.#.#.#\n........#",
+        )
+        .unwrap();
+        let grid = Grid::from_vals(chars.iter().map(|&c| c == '#').collect(), 9, 8);
+
+        let bits = BitGrid::from(&grid);
+        assert_eq!(bits.count_ones(), grid.iter().filter(|&&b| b).count());
+        assert_eq!(Grid::from(&bits), grid);
+        assert!(bits[Point::new(8, 7)]);
+        assert!(!bits[Point::new(7, 7)]);
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut bits = BitGrid::new(70, 2);
+        let p = Point::new(65, 1);
+        bits.set(p, true);
+        assert_eq!(bits.get(p), Some(true));
+        assert_eq!(bits.count_ones(), 1);
+        bits.set(p, false);
+        assert_eq!(bits.get(p), Some(false));
+        assert_eq!(bits.get(Point::new(70, 0)), None);
+        assert_eq!(bits.count_ones(), 0);
+    }
+}
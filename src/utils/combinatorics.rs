@@ -0,0 +1,57 @@
+//! Thin, index- and slice-oriented wrappers around the `itertools` combinatoric adaptors.
+
+use itertools::Itertools;
+
+/// All `k`-element subsets of the indices `0..n`, in lexicographic order.
+pub fn combinations(n: usize, k: usize) -> impl Iterator<Item = Vec<usize>> {
+    (0..n).combinations(k)
+}
+
+/// All orderings of `items`, in lexicographic order of their positions.
+pub fn permutations<T: Clone>(items: &[T]) -> impl Iterator<Item = Vec<T>> + '_ {
+    items.iter().cloned().permutations(items.len())
+}
+
+/// All subsets of `items`, from the empty set upwards in size.
+pub fn power_set<T: Clone>(items: &[T]) -> impl Iterator<Item = Vec<T>> + '_ {
+    items.iter().cloned().powerset()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(combinations(5, 2).count(), 10);
+        assert_eq!(
+            combinations(3, 2).collect::<Vec<_>>(),
+            vec![vec![0, 1], vec![0, 2], vec![1, 2]]
+        );
+        assert_eq!(combinations(3, 0).count(), 1);
+    }
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(
+            permutations(&['a', 'b', 'c']).collect::<Vec<_>>(),
+            vec![
+                vec!['a', 'b', 'c'],
+                vec!['a', 'c', 'b'],
+                vec!['b', 'a', 'c'],
+                vec!['b', 'c', 'a'],
+                vec!['c', 'a', 'b'],
+                vec!['c', 'b', 'a'],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_power_set() {
+        assert_eq!(power_set(&[1, 2, 3, 4]).count(), 16);
+        assert_eq!(
+            power_set(&[1, 2]).collect::<Vec<_>>(),
+            vec![vec![], vec![1], vec![2], vec![1, 2]]
+        );
+    }
+}
@@ -47,21 +47,34 @@ impl DisjointSet {
 
     /// Unions the sets containing `i` and `j`. Returns true if they were merged.
     pub fn union(&mut self, i: usize, j: usize) -> bool {
+        self.link(i, j).is_some()
+    }
+
+    /// Unions the sets containing `i` and `j`, returning `(survivor, absorbed)` roots if
+    /// they were separate.
+    fn link(&mut self, i: usize, j: usize) -> Option<(usize, usize)> {
         let root_i = self.find(i);
         let root_j = self.find(j);
+        if root_i == root_j {
+            return None;
+        }
+
+        let (survivor, absorbed) = if self.size[root_i] < self.size[root_j] {
+            (root_j, root_i)
+        } else {
+            (root_i, root_j)
+        };
+        self.parent[absorbed] = survivor;
+        self.size[survivor] += self.size[absorbed];
+        self.num_sets -= 1;
+        Some((survivor, absorbed))
+    }
 
-        if root_i != root_j {
-            if self.size[root_i] < self.size[root_j] {
-                self.parent[root_i] = root_j;
-                self.size[root_j] += self.size[root_i];
-            } else {
-                self.parent[root_j] = root_i;
-                self.size[root_i] += self.size[root_j];
-            }
-            self.num_sets -= 1;
-            return true;
+    /// Unions every `(i, j)` pair in `edges`.
+    pub fn union_all<I: IntoIterator<Item = (usize, usize)>>(&mut self, edges: I) {
+        for (i, j) in edges {
+            self.union(i, j);
         }
-        false
     }
 
     pub fn size_of(&mut self, i: usize) -> usize {
@@ -70,6 +83,56 @@ impl DisjointSet {
     }
 }
 
+/// A wrapper around DisjointSet that keeps one aggregate value per component, such as a
+/// running total weight, folded together as components merge.
+#[derive(Debug, Clone)]
+pub struct AggregateDisjointSet<T> {
+    inner: DisjointSet,
+    // Only roots hold a value; absorbed roots are emptied on union
+    data: Vec<Option<T>>,
+}
+
+impl<T> AggregateDisjointSet<T> {
+    /// Creates a DSU with one singleton set per element of `data`, each starting with
+    /// its own value as the aggregate.
+    pub fn new(data: Vec<T>) -> Self {
+        Self {
+            inner: DisjointSet::new(data.len()),
+            data: data.into_iter().map(Some).collect(),
+        }
+    }
+
+    pub fn find(&mut self, i: usize) -> usize {
+        self.inner.find(i)
+    }
+
+    /// Unions the sets containing `i` and `j`, folding the absorbed root's aggregate into
+    /// the surviving root's with `merge`. Returns true if they were merged.
+    pub fn union_with<F: FnOnce(&mut T, T)>(&mut self, i: usize, j: usize, merge: F) -> bool {
+        let Some((survivor, absorbed)) = self.inner.link(i, j) else {
+            return false;
+        };
+        if let (Some(taken), Some(kept)) = (self.data[absorbed].take(), &mut self.data[survivor]) {
+            merge(kept, taken);
+        }
+        true
+    }
+
+    /// The aggregate of the set containing `i`.
+    pub fn aggregate(&mut self, i: usize) -> &T {
+        let root = self.inner.find(i);
+        self.data[root].as_ref().expect("root holds data")
+    }
+
+    pub fn size_of(&mut self, i: usize) -> usize {
+        self.inner.size_of(i)
+    }
+
+    pub fn num_sets(&self) -> usize {
+        self.inner.num_sets
+    }
+}
+
 /// A wrapper around DisjointSet that allows using any Hashable type (Strings, Points, etc.)
 pub struct MappingDisjointSet<T>
 where
@@ -128,6 +191,30 @@ where
         self.inner.num_sets
     }
 
+    /// The item stored at index `i`.
+    pub fn data(&self, i: usize) -> &T {
+        &self.reverse_mapping[i]
+    }
+
+    /// The item at the root of the set containing index `i`.
+    pub fn find_root_data(&mut self, i: usize) -> &T {
+        let root = self.inner.find(i);
+        &self.reverse_mapping[root]
+    }
+
+    /// Yields `(index, root, item)` for every element, in index order.
+    pub fn iter(&mut self) -> impl Iterator<Item = (usize, usize, &T)> {
+        // Fully compress first so each parent pointer is the root
+        for i in 0..self.reverse_mapping.len() {
+            self.inner.find(i);
+        }
+        let this = &*self;
+        this.reverse_mapping
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (i, this.inner.parent[i], item))
+    }
+
     pub fn get_all_sets(&mut self) -> Vec<Vec<T>> {
         let mut groups: HashMap<usize, Vec<T>> = HashMap::new();
         for i in 0..self.reverse_mapping.len() {
@@ -208,6 +295,14 @@ mod tests {
         assert_eq!(dsu.parent[2], 0);
     }
 
+    #[test]
+    fn test_union_all_chain() {
+        let mut dsu = DisjointSet::new(6);
+        dsu.union_all((0..5).map(|i| (i, i + 1)));
+        assert_eq!(dsu.num_sets, 1);
+        assert_eq!(dsu.size_of(5), 6);
+    }
+
     #[test]
     fn test_mapping_dsu() {
         let mut dsu = MappingDisjointSet::new();
@@ -234,4 +329,62 @@ mod tests {
         assert_eq!(dsu.num_sets, 1);
         assert_eq!(dsu.size_of(idx), 3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_mapping_dsu_data() {
+        let mut dsu = MappingDisjointSet::new();
+        dsu.union("Alice", "Bob");
+        dsu.union("Bob", "Charlie");
+        let eve = dsu.get_index(&"Eve");
+
+        let alice = dsu.get_index(&"Alice");
+        assert_eq!(dsu.data(alice), &"Alice");
+        let root = *dsu.find_root_data(alice);
+        for name in ["Bob", "Charlie"] {
+            let idx = dsu.get_index(&name);
+            assert_eq!(dsu.find_root_data(idx), &root);
+        }
+        assert_eq!(dsu.find_root_data(eve), &"Eve");
+    }
+
+    #[test]
+    fn test_mapping_dsu_iter() {
+        let mut dsu = MappingDisjointSet::new();
+        dsu.union('a', 'b');
+        dsu.union('c', 'd');
+        dsu.union('b', 'd');
+        dsu.get_index(&'e');
+
+        let entries: Vec<_> = dsu.iter().map(|(i, root, &c)| (i, root, c)).collect();
+        assert_eq!(entries.len(), 5);
+        let root_of = |c| entries.iter().find(|e| e.2 == c).unwrap().1;
+        for c in ['b', 'c', 'd'] {
+            assert_eq!(root_of(c), root_of('a'));
+        }
+        assert_ne!(root_of('e'), root_of('a'));
+        for (i, root, _) in entries {
+            assert_eq!(dsu.inner.find(i), root);
+        }
+    }
+
+    #[test]
+    fn test_aggregate_dsu_sums_weights() {
+        let mut dsu = AggregateDisjointSet::new(vec![1, 2, 3, 4, 5]);
+        let add = |total: &mut i32, other: i32| *total += other;
+        assert!(dsu.union_with(0, 1, add));
+        assert!(dsu.union_with(3, 4, add));
+        assert!(dsu.union_with(1, 3, add));
+        assert!(!dsu.union_with(0, 4, add));
+
+        assert_eq!(dsu.num_sets(), 2);
+        assert_eq!(*dsu.aggregate(4), 1 + 2 + 4 + 5);
+        assert_eq!(*dsu.aggregate(2), 3);
+        let root = dsu.find(0);
+        assert_eq!(*dsu.aggregate(root), 12);
+        assert_eq!(dsu.size_of(root), 4);
+
+        let mut mins = AggregateDisjointSet::new(vec![7, 3, 9]);
+        mins.union_with(0, 2, |m: &mut i32, o| *m = (*m).min(o));
+        assert_eq!(*mins.aggregate(2), 7);
+    }
+}
@@ -0,0 +1,100 @@
+//! Graph algorithms over index-based edge lists.
+
+use crate::utils::disjointset::DisjointSet;
+use std::collections::BinaryHeap;
+
+/// Kruskal's algorithm: returns the edges of a minimum spanning forest of the graph on
+/// nodes `0..n`, in the order they were added (ascending weight).
+///
+/// Edges of equal weight are considered in their input order. If the graph is connected
+/// the result has `n - 1` edges and its last edge is the one that joined the final two
+/// components.
+pub fn kruskal(n: usize, edges: &[(usize, usize, u64)]) -> Vec<(usize, usize, u64)> {
+    let mut sorted = edges.to_vec();
+    sorted.sort_by_key(|&(_, _, w)| w);
+
+    let mut ds = DisjointSet::new(n);
+    let mut tree = Vec::with_capacity(n.saturating_sub(1));
+    for (u, v, w) in sorted {
+        if ds.union(u, v) {
+            tree.push((u, v, w));
+            if ds.num_sets == 1 {
+                break;
+            }
+        }
+    }
+    tree
+}
+
+/// The `k` closest pairs `(i, j, dist(i, j))` with `i < j` among nodes `0..n`, in
+/// ascending order of distance (ties broken by index).
+///
+/// Keeps only the best `k` candidates in a bounded max-heap, so this runs in
+/// $O(n^2 \log k)$ time and $O(k)$ space rather than sorting every pair.
+pub fn k_closest_pairs(
+    n: usize,
+    k: usize,
+    dist: impl Fn(usize, usize) -> u64,
+) -> Vec<(usize, usize, u64)> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for i in 0..n {
+        for j in i + 1..n {
+            let candidate = (dist(i, j), i, j);
+            if heap.len() < k {
+                heap.push(candidate);
+            } else if heap.peek().is_some_and(|worst| candidate < *worst) {
+                heap.pop();
+                heap.push(candidate);
+            }
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|(d, i, j)| (i, j, d))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kruskal() {
+        //   0 --1-- 1
+        //   |     / |
+        //   4   2   3
+        //   | /     |
+        //   2 --5-- 3
+        let edges = [(0, 1, 1), (0, 2, 4), (1, 2, 2), (1, 3, 3), (2, 3, 5)];
+        let mst = kruskal(4, &edges);
+        assert_eq!(mst, vec![(0, 1, 1), (1, 2, 2), (1, 3, 3)]);
+        assert_eq!(mst.iter().map(|&(_, _, w)| w).sum::<u64>(), 6);
+    }
+
+    #[test]
+    fn test_kruskal_disconnected() {
+        let mst = kruskal(4, &[(0, 1, 7), (2, 3, 1)]);
+        assert_eq!(mst, vec![(2, 3, 1), (0, 1, 7)]);
+    }
+
+    #[test]
+    fn test_k_closest_pairs_matches_full_sort() {
+        let xs: [u64; 7] = [40, 3, 17, 29, 8, 33, 11];
+        let dist = |i: usize, j: usize| xs[i].abs_diff(xs[j]);
+
+        let mut all = Vec::new();
+        for i in 0..xs.len() {
+            for j in i + 1..xs.len() {
+                all.push((dist(i, j), i, j));
+            }
+        }
+        all.sort();
+        for k in [0, 1, 5, all.len(), all.len() + 3] {
+            let expected: Vec<_> = all.iter().take(k).map(|&(d, i, j)| (i, j, d)).collect();
+            assert_eq!(k_closest_pairs(xs.len(), k, dist), expected);
+        }
+    }
+}
@@ -5,7 +5,15 @@
 //! coordinate-based operations.
 
 use crate::utils::point::Point;
+use crate::utils::read_lines::two_blocks;
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::convert::Infallible;
 use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::iter::Sum;
+use std::num::ParseIntError;
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 use thiserror::Error;
@@ -16,6 +24,93 @@ pub enum GridError {
     EmptyGrid,
     #[error("inconsistent row lengths")]
     Inconsistent,
+    #[error("invalid cell {cell:?}: {message}")]
+    InvalidCell { cell: char, message: String },
+    #[error("no blank line after the grid")]
+    MissingSection,
+}
+
+/// Outcome of [`Grid::trace_with_turns`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TraceResult {
+    /// The walker left the grid after visiting these positions.
+    Exited(HashSet<Point>),
+    /// The walker returned to a position and heading it had already been in.
+    Loop,
+}
+
+/// Selects rows or columns in axis-agnostic grid operations such as [`Grid::line`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    Row,
+    Col,
+}
+
+/// Selects a family of diagonals for [`Grid::diagonals`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Diagonal {
+    /// Top-left to bottom-right (`↘`).
+    Falling,
+    /// Bottom-left to top-right (`↗`).
+    Rising,
+}
+
+/// Neighbour offsets for a pointy-top hex grid in axial `(q, r)` coordinates, stored with
+/// `q` as `x` and `r` as `y`. Pass to [`Grid::neighbors_with`].
+pub const HEX_DELTAS: [Point; 6] = [
+    Point { x: 1, y: 0 },
+    Point { x: 1, y: -1 },
+    Point { x: 0, y: -1 },
+    Point { x: -1, y: 0 },
+    Point { x: -1, y: 1 },
+    Point { x: 0, y: 1 },
+];
+
+/// The eight compass directions, for puzzles that move diagonally as well as along
+/// rows and columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    /// The unit step in this direction (y-down, so north is `y - 1`).
+    pub fn to_delta(self) -> Point {
+        match self {
+            Direction8::N => Point::UP,
+            Direction8::NE => Point::UP + Point::RIGHT,
+            Direction8::E => Point::RIGHT,
+            Direction8::SE => Point::DOWN + Point::RIGHT,
+            Direction8::S => Point::DOWN,
+            Direction8::SW => Point::DOWN + Point::LEFT,
+            Direction8::W => Point::LEFT,
+            Direction8::NW => Point::UP + Point::LEFT,
+        }
+    }
+
+    /// The direction pointing the other way.
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
 }
 
 /// A 2D grid stored in row-major order.
@@ -39,16 +134,73 @@ impl<T> Grid<T> {
     }
 
     pub fn from_vals(vals: Vec<T>, width: usize, height: usize) -> Self {
-        assert_eq!(
-            vals.len(),
-            width * height,
-            "Buffer size must match dimensions"
-        );
-        Self {
+        Self::try_from_vals(vals, width, height).expect("Buffer size must match dimensions")
+    }
+
+    /// Fallible version of [`Grid::from_vals`], returning
+    /// [`GridError::Inconsistent`] if the buffer length doesn't match the dimensions.
+    pub fn try_from_vals(vals: Vec<T>, width: usize, height: usize) -> Result<Self, GridError> {
+        if vals.len() != width * height {
+            return Err(GridError::Inconsistent);
+        }
+        Ok(Self {
             width,
             height,
             g: vals,
+        })
+    }
+
+    /// Parses a grid whose cells are multi-character tokens separated by `delim`,
+    /// mapping each token through `f`.
+    ///
+    /// Blank lines are skipped and tokens are trimmed of surrounding whitespace.
+    pub fn from_delimited<F: Fn(&str) -> T>(s: &str, delim: char, f: F) -> Result<Self, GridError> {
+        let mut lines = s.lines().filter(|l| !l.trim().is_empty()).peekable();
+        let width = lines
+            .peek()
+            .ok_or(GridError::EmptyGrid)?
+            .split(delim)
+            .count();
+
+        let mut g = Vec::new();
+        let mut height = 0;
+        for line in lines {
+            let start = g.len();
+            g.extend(line.split(delim).map(|token| f(token.trim())));
+            if g.len() - start != width {
+                return Err(GridError::Inconsistent);
+            }
+            height += 1;
         }
+        Ok(Self { width, height, g })
+    }
+
+    /// Parses a grid from `s`, mapping each character through the fallible `f`.
+    ///
+    /// Blank lines are skipped. A failing cell is reported as [`GridError::InvalidCell`].
+    pub fn try_parse_with<E, F>(s: &str, f: F) -> Result<Self, GridError>
+    where
+        E: Display,
+        F: Fn(char) -> Result<T, E>,
+    {
+        let mut lines = s.lines().filter(|l| !l.is_empty()).peekable();
+        let width = lines.peek().ok_or(GridError::EmptyGrid)?.chars().count();
+
+        let mut g = Vec::new();
+        let mut height = 0;
+        for line in lines {
+            if line.chars().count() != width {
+                return Err(GridError::Inconsistent);
+            }
+            for c in line.chars() {
+                g.push(f(c).map_err(|e| GridError::InvalidCell {
+                    cell: c,
+                    message: e.to_string(),
+                })?);
+            }
+            height += 1;
+        }
+        Ok(Self { width, height, g })
     }
 
     #[inline]
@@ -56,6 +208,261 @@ impl<T> Grid<T> {
         p.x >= 0 && p.x < self.width as i32 && p.y >= 0 && p.y < self.height as i32
     }
 
+    /// Returns the cell at `p`, or `None` if `p` is out of bounds (including negative coordinates).
+    #[inline]
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.in_bounds(p).then(|| &self.g[self.to_idx(p)])
+    }
+
+    /// Returns the cell at `p`, or `default` if `p` is out of bounds.
+    ///
+    /// Lets callers treat the grid as surrounded by a border of `default` without
+    /// allocating a padded copy.
+    #[inline]
+    pub fn get_or<'a>(&'a self, p: Point, default: &'a T) -> &'a T {
+        self.get(p).unwrap_or(default)
+    }
+
+    /// Swaps the cells at `a` and `b`.
+    ///
+    /// # Panics
+    /// If either position is out of bounds.
+    pub fn swap(&mut self, a: Point, b: Point) {
+        assert!(
+            self.in_bounds(a) && self.in_bounds(b),
+            "cannot swap {a:?} and {b:?} in a {}x{} grid",
+            self.width,
+            self.height
+        );
+        let (i, j) = (self.to_idx(a), self.to_idx(b));
+        self.g.swap(i, j);
+    }
+
+    /// Swaps the cells at `a` and `b` if both are in bounds, returning whether it did.
+    pub fn try_swap(&mut self, a: Point, b: Point) -> bool {
+        let ok = self.in_bounds(a) && self.in_bounds(b);
+        if ok {
+            self.swap(a, b);
+        }
+        ok
+    }
+
+    /// Returns whether `p` lies on the outermost ring of the grid.
+    #[inline]
+    pub fn is_border(&self, p: Point) -> bool {
+        self.in_bounds(p)
+            && (p.x == 0
+                || p.y == 0
+                || p.x == self.width as i32 - 1
+                || p.y == self.height as i32 - 1)
+    }
+
+    /// Returns the cell at `p`, wrapping both coordinates around the grid
+    /// (torus topology) so any signed coordinate maps to a valid cell.
+    ///
+    /// # Panics
+    /// If the grid has zero width or height, as there is no cell to wrap onto.
+    #[inline]
+    pub fn wrapping_get(&self, p: Point) -> &T {
+        let x = p.x.rem_euclid(self.width as i32);
+        let y = p.y.rem_euclid(self.height as i32);
+        &self[Point::new(x, y)]
+    }
+
+    /// Iterates over every cell in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.g.iter()
+    }
+
+    /// Groups every position by the value stored there, in row-major order within each group.
+    pub fn index_by_value(&self) -> HashMap<T, Vec<Point>>
+    where
+        T: Hash + Eq + Clone,
+    {
+        let mut index: HashMap<T, Vec<Point>> = HashMap::new();
+        for (p, val) in self.all_positions(|_| true).zip(&self.g) {
+            index.entry(val.clone()).or_default().push(p);
+        }
+        index
+    }
+
+    /// Sum of every cell in the grid.
+    pub fn sum(&self) -> T
+    where
+        T: Sum + Copy,
+    {
+        self.g.iter().copied().sum()
+    }
+
+    /// Sum of the cells visited by `path`, counting repeated positions each time.
+    ///
+    /// # Panics
+    /// If any position in `path` is out of bounds.
+    pub fn path_cost(&self, path: &[Point]) -> T
+    where
+        T: Sum + Copy,
+    {
+        path.iter().map(|&p| self[p]).sum()
+    }
+
+    /// Largest cell value, or `None` for an empty grid.
+    pub fn max(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.g.iter().max()
+    }
+
+    /// Smallest cell value, or `None` for an empty grid.
+    pub fn min(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.g.iter().min()
+    }
+
+    /// Cells of row `y`, left to right.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        self.line(Axis::Row, y)
+    }
+
+    /// Row `y` as a contiguous slice, for callers that want slice methods like `windows`.
+    ///
+    /// # Panics
+    /// If `y` is outside the grid.
+    pub fn row_slice(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "Row {y} out of bounds");
+        &self.g[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every diagonal of the given family as a list of positions, each walked in the
+    /// family's direction.
+    ///
+    /// Diagonals are yielded starting from the one through the top-left corner for
+    /// [`Diagonal::Rising`], or the bottom-left corner for [`Diagonal::Falling`], and
+    /// sweeping across to the opposite corner.
+    pub fn diagonals(&self, family: Diagonal) -> impl Iterator<Item = Vec<Point>> + '_ {
+        let (w, h) = (self.width as i32, self.height as i32);
+        let count = if w == 0 || h == 0 { 0 } else { w + h - 1 };
+        (0..count).map(move |k| {
+            let (start, dir) = match family {
+                // k runs over x - y + (h - 1)
+                Diagonal::Falling => {
+                    let d = k - (h - 1);
+                    (Point::new(d.max(0), (-d).max(0)), Point::new(1, 1))
+                }
+                // k runs over x + y
+                Diagonal::Rising => {
+                    let y = k.min(h - 1);
+                    (Point::new(k - y, y), Point::new(1, -1))
+                }
+            };
+            self.ray(start, dir).collect()
+        })
+    }
+
+    /// Cells of column `x`, top to bottom.
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.line(Axis::Col, x)
+    }
+
+    /// Cells of the row or column at `index` along `axis`.
+    ///
+    /// # Panics
+    /// If `index` is outside the grid along that axis.
+    pub fn line(&self, axis: Axis, index: usize) -> impl Iterator<Item = &T> {
+        let (start, step, len, bound) = match axis {
+            Axis::Row => (index * self.width, 1, self.width, self.height),
+            Axis::Col => (index, self.width, self.height, self.width),
+        };
+        assert!(index < bound, "{axis:?} {index} out of bounds");
+        self.g[start..].iter().step_by(step).take(len)
+    }
+
+    /// Whether any row (for [`Axis::Row`]) or column (for [`Axis::Col`]) contains at
+    /// least `min_len` consecutive cells satisfying `pred`.
+    pub fn has_run(&self, axis: Axis, min_len: usize, pred: impl Fn(&T) -> bool) -> bool {
+        let lines = match axis {
+            Axis::Row => self.height,
+            Axis::Col => self.width,
+        };
+        (0..lines).any(|i| {
+            let mut run = 0;
+            self.line(axis, i).any(|cell| {
+                run = if pred(cell) { run + 1 } else { 0 };
+                run >= min_len
+            })
+        })
+    }
+
+    /// Whether `other` has the same shape and agrees with `self` on every cell for
+    /// which `ignore` returns `false`.
+    pub fn eq_ignoring<F: Fn(Point) -> bool>(&self, other: &Grid<T>, ignore: F) -> bool
+    where
+        T: Eq,
+    {
+        self.width == other.width
+            && self.height == other.height
+            && self.g.iter().zip(&other.g).enumerate().all(|(i, (a, b))| {
+                a == b || ignore(Point::new((i % self.width) as i32, (i / self.width) as i32))
+            })
+    }
+
+    /// Consuming [`Grid::transpose`] that moves cells instead of cloning them.
+    pub fn into_transpose(self) -> Self {
+        let (w, h) = (self.width, self.height);
+        self.into_permuted(h, w, |i| (i % h) * w + i / h)
+    }
+
+    /// Consuming [`Grid::rotate_right`] that moves cells instead of cloning them.
+    pub fn into_rotate_right(self) -> Self {
+        let (w, h) = (self.width, self.height);
+        self.into_permuted(h, w, |i| (h - 1 - i % h) * w + i / h)
+    }
+
+    /// Consuming [`Grid::flip_lr`] that moves cells instead of cloning them.
+    pub fn into_flip_lr(self) -> Self {
+        let (w, h) = (self.width, self.height);
+        self.into_permuted(w, h, |i| (i / w) * w + (w - 1 - i % w))
+    }
+
+    /// Rebuilds the grid with the given dimensions, where `src` maps each new index
+    /// to the old index whose cell should be moved there.
+    fn into_permuted(self, width: usize, height: usize, src: impl Fn(usize) -> usize) -> Self {
+        let mut cells: Vec<Option<T>> = self.g.into_iter().map(Some).collect();
+        let g = (0..cells.len())
+            .map(|i| {
+                cells[src(i)]
+                    .take()
+                    .expect("permutation visits each cell once")
+            })
+            .collect();
+        Self::from_vals(g, width, height)
+    }
+
+    /// Checkerboard colour of `p`: `true` when `x + y` is even.
+    ///
+    /// Every step between cardinal neighbours flips the parity, so a cell reachable in
+    /// exactly `n` steps from `start` has the same parity as `start` iff `n` is even.
+    pub fn parity(p: Point) -> bool {
+        (p.x ^ p.y) & 1 == 0
+    }
+
+    /// Positions whose [`Grid::parity`] equals `even`, in row-major order.
+    pub fn positions_with_parity(&self, even: bool) -> impl Iterator<Item = Point> + '_ {
+        self.all_positions(|_| true)
+            .filter(move |&p| Self::parity(p) == even)
+    }
+
+    /// Cardinal neighbours of `p` on a torus, each paired with whether reaching it
+    /// wrapped across an edge of the grid.
+    pub fn neighbors_with_wrap_flag(&self, p: Point) -> impl Iterator<Item = (Point, bool)> + '_ {
+        p.neighbors4().map(move |n| {
+            let wrapped = n.wrap(self.width as i32, self.height as i32);
+            (wrapped, wrapped != n)
+        })
+    }
+
     #[inline]
     fn to_idx(&self, p: Point) -> usize {
         (p.y as usize * self.width) + p.x as usize
@@ -83,19 +490,382 @@ impl<T> Grid<T> {
     }
 
     pub fn cardinal_neighbors(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
-        [Point::UP, Point::DOWN, Point::LEFT, Point::RIGHT]
-            .into_iter()
-            .map(move |dir| p + dir)
-            .filter(move |&pos| self.in_bounds(pos))
+        p.neighbors4().filter(move |&pos| self.in_bounds(pos))
     }
 
     pub fn all_neighbors(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
-        (-1..=1)
-            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
-            .filter(|&(dx, dy)| dx != 0 || dy != 0)
-            .map(move |(dx, dy)| p + Point::new(dx, dy))
+        p.neighbors8().filter(move |&pos| self.in_bounds(pos))
+    }
+
+    /// Yields the in-bounds cells offset from `p` by each of `deltas`, for custom
+    /// adjacencies such as [`HEX_DELTAS`].
+    pub fn neighbors_with(&self, p: Point, deltas: &[Point]) -> impl Iterator<Item = Point> {
+        deltas
+            .iter()
+            .map(move |&d| p + d)
             .filter(move |&pos| self.in_bounds(pos))
     }
+
+    /// Lazily yields positions from `start` stepping by `dir` until the ray leaves the grid.
+    ///
+    /// `start` itself is the first item if it is in bounds. A zero `dir` never terminates.
+    pub fn ray(&self, start: Point, dir: Point) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(Some(start), move |&p| Some(p + dir))
+            .take_while(move |&p| self.in_bounds(p))
+    }
+
+    /// Renders the grid row by row, mapping each cell to a `char` with `f`.
+    ///
+    /// Like the [`Display`] impl, every row ends with a newline.
+    pub fn render<F: Fn(&T) -> char>(&self, f: F) -> String {
+        // A zero-width grid has no cells, but `chunks` still rejects a size of zero
+        self.g
+            .chunks(self.width.max(1))
+            .flat_map(|row| row.iter().map(&f).chain(['\n']))
+            .collect()
+    }
+
+    /// Like [`Grid::cardinal_neighbors`], but also yields the value at each neighbour.
+    pub fn cardinal_neighbor_values(&self, p: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.cardinal_neighbors(p).map(move |pos| (pos, &self[pos]))
+    }
+
+    /// Like [`Grid::cardinal_neighbors`], but pairs each neighbour with the unit direction
+    /// ([`Point::UP`], [`Point::DOWN`], ...) stepped to reach it.
+    pub fn cardinal_neighbor_dirs(&self, p: Point) -> impl Iterator<Item = (Point, Point)> + '_ {
+        [Point::UP, Point::DOWN, Point::LEFT, Point::RIGHT]
+            .into_iter()
+            .map(move |dir| (dir, p + dir))
+            .filter(move |&(_, pos)| self.in_bounds(pos))
+    }
+
+    /// Like [`Grid::cardinal_neighbors`], but pairs each neighbour with the cost of moving
+    /// onto it, found by applying `cost` to its value.
+    pub fn weighted_cardinal_neighbors<F: Fn(&T) -> usize>(
+        &self,
+        p: Point,
+        cost: F,
+    ) -> impl Iterator<Item = (Point, usize)> {
+        self.cardinal_neighbor_values(p)
+            .map(move |(pos, val)| (pos, cost(val)))
+    }
+
+    /// Like [`Grid::all_neighbors`], but also yields the value at each neighbour.
+    pub fn all_neighbor_values(&self, p: Point) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.all_neighbors(p).map(move |pos| (pos, &self[pos]))
+    }
+
+    /// Depth-first preorder traversal of the 4-connected region of `can_visit` cells
+    /// containing `start`, calling `visit` once on each cell as it is first reached.
+    ///
+    /// Nothing is visited if `start` is off the grid or fails `can_visit`.
+    pub fn dfs<F: FnMut(Point)>(&self, start: Point, can_visit: impl Fn(&T) -> bool, mut visit: F) {
+        if !self.get(start).is_some_and(&can_visit) {
+            return;
+        }
+        let mut seen = vec![false; self.g.len()];
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            let idx = self.to_idx(pos);
+            if std::mem::replace(&mut seen[idx], true) {
+                continue;
+            }
+            visit(pos);
+            stack.extend(
+                pos.neighbors4()
+                    .filter(|&n| self.get(n).is_some_and(&can_visit) && !seen[self.to_idx(n)]),
+            );
+        }
+    }
+
+    /// Counts the paths that descend one row per step from any of `starts`, moving
+    /// straight down or one column diagonally, where `can_step(from, to)` allows each edge.
+    ///
+    /// Each cell holds the number of paths reaching it. The predicate takes positions
+    /// rather than values so that it can look at cells beside the edge, such as a splitter
+    /// directly below `from` that deflects the path sideways.
+    pub fn count_monotone_paths<F>(&self, starts: &[Point], can_step: F) -> Grid<u64>
+    where
+        F: Fn(Point, Point) -> bool,
+    {
+        let mut paths = Grid::new(0, self.width, self.height);
+        for &s in starts.iter().filter(|&&s| self.in_bounds(s)) {
+            paths[s] += 1;
+        }
+        for y in 0..self.height.saturating_sub(1) as i32 {
+            for x in 0..self.width as i32 {
+                let from = Point::new(x, y);
+                let count = paths[from];
+                if count == 0 {
+                    continue;
+                }
+                for dx in -1..=1 {
+                    let to = Point::new(x + dx, y + 1);
+                    if self.in_bounds(to) && can_step(from, to) {
+                        paths[to] += count;
+                    }
+                }
+            }
+        }
+        paths
+    }
+
+    /// Breadth-first search seeded from every in-bounds source at once, stepping between
+    /// 4-connected `passable` cells.
+    ///
+    /// Each cell holds its distance to the nearest source, or `None` if unreachable.
+    pub fn bfs_multi_source<F>(&self, sources: &[Point], passable: F) -> Grid<Option<usize>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut dist = Grid::new(None, self.width, self.height);
+        let mut queue = VecDeque::new();
+        for &s in sources.iter().filter(|&&s| self.in_bounds(s)) {
+            if dist[s].is_none() {
+                dist[s] = Some(0);
+                queue.push_back(s);
+            }
+        }
+
+        while let Some(pos) = queue.pop_front() {
+            let d = dist[pos].unwrap();
+            for (next, val) in self.cardinal_neighbor_values(pos) {
+                if dist[next].is_none() && passable(val) {
+                    dist[next] = Some(d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        dist
+    }
+
+    /// Dijkstra from `start` over cardinal moves, where entering a cell costs `cost` of
+    /// its value and `None` marks it impassable. The start cell itself is free.
+    ///
+    /// Each cell holds its cheapest distance from `start`, or `None` if it is a wall or
+    /// unreachable. Everything is `None` if `start` is off the grid or impassable.
+    pub fn dijkstra_from<F>(&self, start: Point, cost: F) -> Grid<Option<usize>>
+    where
+        F: Fn(&T) -> Option<usize>,
+    {
+        let mut dist = Grid::new(None, self.width, self.height);
+        if self.get(start).and_then(&cost).is_none() {
+            return dist;
+        }
+        let mut heap = BinaryHeap::from([Reverse((0, start))]);
+        dist[start] = Some(0);
+
+        while let Some(Reverse((d, pos))) = heap.pop() {
+            if dist[pos].is_some_and(|best| d > best) {
+                continue;
+            }
+            for (next, val) in self.cardinal_neighbor_values(pos) {
+                let Some(step) = cost(val) else {
+                    continue;
+                };
+                if dist[next].is_none_or(|best| d + step < best) {
+                    dist[next] = Some(d + step);
+                    heap.push(Reverse((d + step, next)));
+                }
+            }
+        }
+        dist
+    }
+
+    /// Breadth-first flood from `start` over 4-connected cells that are not `is_blocked`,
+    /// mapping each reachable cell to its hop distance from the seed.
+    ///
+    /// The map is empty if `start` is off the grid or blocked.
+    pub fn flood_fill_distances<F>(&self, start: Point, is_blocked: F) -> HashMap<Point, usize>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut dist = HashMap::new();
+        if self.get(start).is_none_or(&is_blocked) {
+            return dist;
+        }
+        dist.insert(start, 0);
+        let mut queue = VecDeque::from([start]);
+        while let Some(pos) = queue.pop_front() {
+            let d = dist[&pos];
+            for (next, val) in self.cardinal_neighbor_values(pos) {
+                if !is_blocked(val) && !dist.contains_key(&next) {
+                    dist.insert(next, d + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        dist
+    }
+
+    /// Runs `f` on each of `starts` across rayon's thread pool, for puzzles that need an
+    /// independent search per source cell. Results are in the same order as `starts`.
+    pub fn par_for_each_start<R, F>(&self, starts: impl IntoIterator<Item = Point>, f: F) -> Vec<R>
+    where
+        R: Send,
+        F: Fn(Point) -> R + Sync,
+    {
+        let starts: Vec<_> = starts.into_iter().collect();
+        starts.into_par_iter().map(&f).collect()
+    }
+
+    /// Every cell reachable from `start` in at most `steps` 4-connected moves over
+    /// `passable` cells, including `start` itself.
+    ///
+    /// The set is empty if `start` is off the grid or not passable.
+    pub fn reachable_within<F>(&self, start: Point, steps: usize, passable: F) -> HashSet<Point>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut seen = HashSet::new();
+        if !self.get(start).is_some_and(&passable) {
+            return seen;
+        }
+        seen.insert(start);
+        let mut frontier = vec![start];
+        for _ in 0..steps {
+            frontier = frontier
+                .into_iter()
+                .flat_map(|pos| self.cardinal_neighbor_values(pos))
+                .filter(|&(next, val)| passable(val) && seen.insert(next))
+                .map(|(next, _)| next)
+                .collect();
+            if frontier.is_empty() {
+                break;
+            }
+        }
+        seen
+    }
+
+    /// Walks from `start` heading `start_dir`, turning right whenever the next cell is
+    /// `blocked`, until the walker leaves the grid or starts repeating itself.
+    ///
+    /// `blocked` sees each cell's position as well as its value, so callers can add
+    /// obstacles without copying the grid.
+    pub fn trace_with_turns<F>(&self, start: Point, start_dir: Point, blocked: F) -> TraceResult
+    where
+        F: Fn(Point, &T) -> bool,
+    {
+        let mut seen = HashSet::new();
+        let mut curr = start;
+        let mut dir = start_dir;
+
+        loop {
+            if !seen.insert((curr, dir)) {
+                return TraceResult::Loop;
+            }
+            let next = curr + dir;
+            match self.get(next) {
+                None => break,
+                Some(cell) if blocked(next, cell) => dir = dir.rotate_right_90(),
+                Some(_) => curr = next,
+            }
+        }
+        TraceResult::Exited(seen.into_iter().map(|(p, _)| p).collect())
+    }
+
+    /// Labels each 4-connected region of equal cells with a 0-based id, returning the
+    /// label grid and the number of regions.
+    pub fn label_components(&self) -> (Grid<usize>, usize)
+    where
+        T: Eq,
+    {
+        let mut labels = Grid::new(usize::MAX, self.width, self.height);
+        let mut count = 0;
+        let mut stack = Vec::new();
+
+        for start in self.all_positions(|_| true) {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+            labels[start] = count;
+            stack.push(start);
+            while let Some(pos) = stack.pop() {
+                for (next, val) in self.cardinal_neighbor_values(pos) {
+                    if labels[next] == usize::MAX && *val == self[pos] {
+                        labels[next] = count;
+                        stack.push(next);
+                    }
+                }
+            }
+            count += 1;
+        }
+        (labels, count)
+    }
+
+    /// Number of separate 4-connected regions formed by each cell value.
+    pub fn region_counts(&self) -> HashMap<T, usize>
+    where
+        T: Eq + Hash + Clone,
+    {
+        let (labels, count) = self.label_components();
+        let mut seen = vec![false; count];
+        let mut counts = HashMap::new();
+        for (val, &label) in self.g.iter().zip(&labels.g) {
+            if !std::mem::replace(&mut seen[label], true) {
+                *counts.entry(val.clone()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// Perimeter of a maximal region of equal cells, counting each cell edge that borders
+    /// a different value or the edge of the grid.
+    pub fn region_perimeter(&self, region: &[Point]) -> usize
+    where
+        T: Eq,
+    {
+        region
+            .iter()
+            .map(|&p| {
+                p.neighbors4()
+                    .filter(|&n| self.get(n) != Some(&self[p]))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The `(vertical, horizontal, diagonal)` neighbours of `p` for each of its four
+    /// corners, in the order top-left, top-right, bottom-left, bottom-right.
+    ///
+    /// Off-grid neighbours are `None`.
+    pub fn corner_context(&self, p: Point) -> [(Option<&T>, Option<&T>, Option<&T>); 4] {
+        // Each quadrant around a cell as (vertical, horizontal); the diagonal is their sum
+        const QUADRANTS: [(Point, Point); 4] = [
+            (Point::UP, Point::LEFT),
+            (Point::UP, Point::RIGHT),
+            (Point::DOWN, Point::LEFT),
+            (Point::DOWN, Point::RIGHT),
+        ];
+        QUADRANTS.map(|(v, h)| (self.get(p + v), self.get(p + h), self.get(p + v + h)))
+    }
+
+    /// Number of straight sides of a maximal region of equal cells, found by counting its
+    /// corners (a polygon has as many sides as corners).
+    pub fn region_sides(&self, region: &[Point]) -> usize
+    where
+        T: Eq,
+    {
+        region.iter().map(|&p| self.corners_at(p, &self[p])).sum()
+    }
+
+    /// Number of corners of the `value` region boundary that meet at the cell `pos`,
+    /// between 0 and 4.
+    pub fn corners_at(&self, pos: Point, value: &T) -> usize
+    where
+        T: Eq,
+    {
+        let differs = |cell: Option<&T>| cell != Some(value);
+        self.corner_context(pos)
+            .into_iter()
+            .filter(|&(v, h, d)| {
+                let (v_diff, h_diff, d_diff) = (differs(v), differs(h), differs(d));
+                // Outer corner: both cardinal neighbours differ.
+                // Inner corner: both cardinals match but the diagonal differs.
+                (v_diff && h_diff) || (!v_diff && !h_diff && d_diff)
+            })
+            .count()
+    }
 }
 
 impl<T: Clone + Eq> Grid<T> {
@@ -121,6 +891,13 @@ impl<T: Clone + Eq> Grid<T> {
         changed
     }
 
+    /// Even-odd test for whether `pos` lies strictly inside a closed boundary drawn
+    /// one cell thick, casting a ray to the right.
+    ///
+    /// Each maximal run of boundary cells on the ray is one potential crossing. A lone
+    /// cell is a vertical edge and crosses if it continues upwards. A longer run is a
+    /// horizontal edge and crosses only if its two ends turn in opposite vertical
+    /// directions; if both turn the same way the ray merely grazes the vertex.
     pub fn is_inside_polygon<F>(&self, pos: Point, boundary_fn: F) -> bool
     where
         F: Fn(&T) -> bool,
@@ -128,31 +905,113 @@ impl<T: Clone + Eq> Grid<T> {
         if boundary_fn(&self[pos]) {
             return false;
         }
+        let is_boundary = |p: Point| self.get(p).is_some_and(&boundary_fn);
+
         let mut crossings = 0;
-        for x in pos.x..self.width as i32 {
-            let p = Point::new(x, pos.y);
-            if boundary_fn(&self[p])
-                && pos.y > 0 && boundary_fn(&self[p + Point::UP]) {
-                    crossings += 1;
-                }
+        let mut x = pos.x + 1;
+        while x < self.width as i32 {
+            if !is_boundary(Point::new(x, pos.y)) {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while is_boundary(Point::new(x + 1, pos.y)) {
+                x += 1;
+            }
+            let up_start = is_boundary(Point::new(start, pos.y - 1));
+            let up_end = is_boundary(Point::new(x, pos.y - 1));
+            if (start == x && up_start) || (start != x && up_start != up_end) {
+                crossings += 1;
+            }
+            x += 1;
         }
         crossings % 2 == 1
     }
 }
 
-impl<T: Clone> Grid<T> {
-    pub fn ray_cast(&self, start: Point, dir: Point, steps: usize) -> Vec<T> {
-        let mut elements = Vec::with_capacity(steps);
-        let mut curr = start;
-
-        for _ in 0..steps {
-            if !self.in_bounds(curr) {
-                break;
+impl<T: Clone + Eq + Hash> Grid<T> {
+    /// Applies `step` to the grid `target` times, detecting when a full-grid state repeats
+    /// and skipping ahead by whole periods rather than simulating every iteration.
+    pub fn iterate_until_cycle<F>(&self, step: F, target: usize) -> Self
+    where
+        F: Fn(&Self) -> Self,
+    {
+        let mut seen = HashMap::from([(self.clone(), 0)]);
+        let mut states = vec![self.clone()];
+        for i in 1..=target {
+            let next = step(&states[i - 1]);
+            if let Some(&start) = seen.get(&next) {
+                let period = i - start;
+                return states.swap_remove(start + (target - start) % period);
             }
-            elements.push(self[curr].clone());
-            curr += dir;
+            seen.insert(next.clone(), i);
+            states.push(next);
         }
-        elements
+        states.swap_remove(target)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Fills the 4-connected region of cells reachable from `start` that satisfy
+    /// `can_enter`, returning the number of cells filled.
+    ///
+    /// Unlike [`Grid::flood_fill`] this does not compare cells against `fill`, so the
+    /// region can be defined by any predicate rather than by equality.
+    pub fn flood_fill_if<F>(&mut self, start: Point, fill: T, can_enter: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        if !self.in_bounds(start) || !can_enter(&self[start]) {
+            return 0;
+        }
+
+        let mut visited = vec![false; self.g.len()];
+        visited[self.to_idx(start)] = true;
+        let mut stack = vec![start];
+        let mut changed = 0;
+        while let Some(pos) = stack.pop() {
+            for n in pos.neighbors4() {
+                if self.get(n).is_some_and(&can_enter) && !visited[self.to_idx(n)] {
+                    visited[self.to_idx(n)] = true;
+                    stack.push(n);
+                }
+            }
+            self[pos] = fill.clone();
+            changed += 1;
+        }
+        changed
+    }
+
+    /// Moves `actor` one step in `dir`, pushing any contiguous line of boxes ahead of it.
+    ///
+    /// Nothing moves if the line ends in a wall or the edge of the grid. The vacated cell
+    /// is set to `empty`. Returns the actor's position after the move.
+    pub fn push<W, B>(&mut self, actor: Point, dir: Point, is_wall: W, is_box: B, empty: T) -> Point
+    where
+        W: Fn(&T) -> bool,
+        B: Fn(&T) -> bool,
+    {
+        let next = actor + dir;
+        let mut scan = next;
+        while self.get(scan).is_some_and(&is_box) {
+            scan += dir;
+        }
+        if self.get(scan).is_none_or(&is_wall) {
+            return actor;
+        }
+
+        if scan != next {
+            self[scan] = self[next].clone();
+        }
+        self[next] = std::mem::replace(&mut self[actor], empty);
+        next
+    }
+
+    pub fn ray_cast(&self, start: Point, dir: Point, steps: usize) -> Vec<T> {
+        self.ray(start, dir)
+            .take(steps)
+            .map(|p| self[p].clone())
+            .collect()
     }
 
     pub fn rotate_right(&self) -> Self {
@@ -174,6 +1033,71 @@ impl<T: Clone> Grid<T> {
         Self::from_vals(g, self.width, self.height)
     }
 
+    /// Copies the grid out as a vector of rows, the inverse of
+    /// `Grid::try_from(Vec<Vec<T>>)`.
+    pub fn to_rows(&self) -> Vec<Vec<T>> {
+        self.g.chunks(self.width).map(<[T]>::to_vec).collect()
+    }
+
+    /// Swaps rows and columns, so the cell at `(x, y)` moves to `(y, x)`.
+    pub fn transpose(&self) -> Self {
+        let mut g = Vec::with_capacity(self.g.len());
+        for x in 0..self.width {
+            for y in 0..self.height {
+                g.push(self[Point::new(x as i32, y as i32)].clone());
+            }
+        }
+        Self::from_vals(g, self.height, self.width)
+    }
+
+    /// Builds a grid from possibly ragged `lines`, right-padding short rows with `fill`
+    /// up to the length of the longest row.
+    pub fn from_lines_padded<'a>(lines: impl IntoIterator<Item = &'a str>, fill: T) -> Self
+    where
+        T: From<char>,
+    {
+        let rows: Vec<Vec<T>> = lines
+            .into_iter()
+            .map(|line| line.chars().map(T::from).collect())
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+
+        let mut g = Vec::with_capacity(width * height);
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            g.extend(row);
+        }
+        Self::from_vals(g, width, height)
+    }
+
+    /// Inserts a new row filled with `fill` before row `at`, shifting later rows down.
+    ///
+    /// This shifts every cell after the insertion point, so costs O(width * height).
+    /// Panics if `at > height`.
+    pub fn insert_row(&mut self, at: usize, fill: T) {
+        assert!(at <= self.height, "row index out of range");
+        let idx = at * self.width;
+        self.g.splice(idx..idx, std::iter::repeat_n(fill, self.width));
+        self.height += 1;
+    }
+
+    /// Inserts a new column filled with `fill` before column `at`, shifting later columns right.
+    ///
+    /// This rebuilds the whole buffer, so costs O(width * height).
+    /// Panics if `at > width`.
+    pub fn insert_col(&mut self, at: usize, fill: T) {
+        assert!(at <= self.width, "column index out of range");
+        let mut g = Vec::with_capacity((self.width + 1) * self.height);
+        for row in self.g.chunks(self.width) {
+            g.extend_from_slice(&row[..at]);
+            g.push(fill.clone());
+            g.extend_from_slice(&row[at..]);
+        }
+        self.g = g;
+        self.width += 1;
+    }
+
     pub fn expand(&self, fill: T) -> Self {
         let new_w = self.width + 2;
         let mut g = Vec::with_capacity(new_w * (self.height + 2));
@@ -186,6 +1110,238 @@ impl<T: Clone> Grid<T> {
         g.extend(std::iter::repeat_n(fill.clone(), new_w));
         Self::from_vals(g, new_w, self.height + 2)
     }
+
+    /// Sets every cell of the `width` x `height` rectangle starting at `top_left` to
+    /// `value`, ignoring any part of the rectangle that falls outside the grid.
+    pub fn fill_rect(&mut self, top_left: Point, width: usize, height: usize, value: T) {
+        let clip = |start: i32, len: usize, bound: usize| {
+            let end = (i64::from(start) + len as i64).clamp(0, bound as i64) as usize;
+            (start.max(0) as usize).min(end)..end
+        };
+        let xs = clip(top_left.x, width, self.width);
+        for y in clip(top_left.y, height, self.height) {
+            let row = y * self.width;
+            self.g[row + xs.start..row + xs.end].fill(value.clone());
+        }
+    }
+
+    /// Places `self` in the middle of a new `width` x `height` grid of `fill`.
+    ///
+    /// When the margin is odd the extra cell goes on the right or bottom. Returns `None`
+    /// if the target is smaller than `self` in either dimension.
+    pub fn centered_in(&self, width: usize, height: usize, fill: T) -> Option<Self> {
+        if width < self.width || height < self.height {
+            return None;
+        }
+        let (x0, y0) = ((width - self.width) / 2, (height - self.height) / 2);
+        let mut out = Self::new(fill, width, height);
+        for (y, row) in self.g.chunks(self.width).enumerate() {
+            let start = (y0 + y) * width + x0;
+            out.g[start..start + self.width].clone_from_slice(row);
+        }
+        Some(out)
+    }
+
+    /// Copies the `width` x `height` rectangle starting at `top_left` into a new grid.
+    ///
+    /// Returns `None` if the rectangle does not fit inside the grid.
+    pub fn crop(&self, top_left: Point, width: usize, height: usize) -> Option<Self> {
+        let x0 = usize::try_from(top_left.x).ok()?;
+        let y0 = usize::try_from(top_left.y).ok()?;
+        if x0.checked_add(width).is_none_or(|right| right > self.width)
+            || y0
+                .checked_add(height)
+                .is_none_or(|bottom| bottom > self.height)
+        {
+            return None;
+        }
+        let g = (y0..y0 + height)
+            .flat_map(|y| {
+                let start = y * self.width + x0;
+                self.g[start..start + width].iter().cloned()
+            })
+            .collect();
+        Some(Self::from_vals(g, width, height))
+    }
+
+    /// Strips `n` cells from every side, undoing `n` rounds of [`Grid::expand`].
+    ///
+    /// Returns `None` if nothing would be left of the interior.
+    pub fn unpad(&self, n: usize) -> Option<Self> {
+        let width = self.width.checked_sub(2 * n).filter(|&w| w > 0)?;
+        let height = self.height.checked_sub(2 * n).filter(|&h| h > 0)?;
+        let offset = i32::try_from(n).ok()?;
+        self.crop(Point::new(offset, offset), width, height)
+    }
+}
+
+impl<T: Copy + Into<u64>> Grid<T> {
+    /// Builds the 2D prefix-sum table, where each cell holds the sum of all cells above
+    /// and to the left of it (inclusive). Query it with [`Grid::rect_sum`].
+    pub fn prefix_sum(&self) -> Grid<u64> {
+        let mut psum = Grid::new(0, self.width, self.height);
+        for y in 0..self.height {
+            let mut row_sum = 0;
+            for x in 0..self.width {
+                row_sum += self.g[y * self.width + x].into();
+                let above = if y > 0 { psum.g[(y - 1) * self.width + x] } else { 0 };
+                psum.g[y * self.width + x] = row_sum + above;
+            }
+        }
+        psum
+    }
+}
+
+impl Grid<u64> {
+    /// Sums the inclusive rectangle `top_left..=bottom_right` of the grid this
+    /// prefix-sum table was built from.
+    pub fn rect_sum(&self, top_left: Point, bottom_right: Point) -> u64 {
+        let at = |x: i32, y: i32| {
+            if x < 0 || y < 0 {
+                0
+            } else {
+                self[Point::new(x, y)]
+            }
+        };
+        let (x1, y1, x2, y2) = (top_left.x, top_left.y, bottom_right.x, bottom_right.y);
+        at(x2, y2) + at(x1 - 1, y1 - 1) - at(x1 - 1, y2) - at(x2, y1 - 1)
+    }
+}
+
+impl Grid<u32> {
+    /// Cheapest cost from `start` to `end` using cardinal moves, where entering a cell
+    /// costs its value. The start cell itself is free.
+    pub fn weighted_dijkstra(&self, start: Point, end: Point) -> Option<usize> {
+        if !self.in_bounds(start) || !self.in_bounds(end) {
+            return None;
+        }
+        let mut dist = vec![usize::MAX; self.g.len()];
+        let mut heap = BinaryHeap::new();
+        dist[self.to_idx(start)] = 0;
+        heap.push(Reverse((0, start)));
+
+        while let Some(Reverse((cost, pos))) = heap.pop() {
+            if pos == end {
+                return Some(cost);
+            }
+            if cost > dist[self.to_idx(pos)] {
+                continue;
+            }
+            for (next, &weight) in self.cardinal_neighbor_values(pos) {
+                let next_cost = cost + weight as usize;
+                let idx = self.to_idx(next);
+                if next_cost < dist[idx] {
+                    dist[idx] = next_cost;
+                    heap.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl Grid<bool> {
+    /// Returns the positions of all `true` cells.
+    pub fn to_sparse(&self) -> HashSet<Point> {
+        self.all_positions(|&b| b).collect()
+    }
+
+    /// Builds a `width` x `height` grid with `true` at each of `positions`.
+    ///
+    /// Positions outside the grid are ignored.
+    pub fn from_sparse(positions: &HashSet<Point>, width: usize, height: usize) -> Self {
+        let mut grid = Self::new(false, width, height);
+        for &p in positions {
+            if grid.in_bounds(p) {
+                grid[p] = true;
+            }
+        }
+        grid
+    }
+}
+
+impl Grid<char> {
+    /// Parses a block whose first line is a label (such as `0:`) and whose remaining
+    /// lines form the grid, returning the label without surrounding whitespace or its
+    /// trailing colon.
+    ///
+    /// Returns [`GridError::EmptyGrid`] if there is nothing below the label.
+    pub fn from_labeled_block(s: &str) -> Result<(String, Grid<char>), GridError> {
+        let (label, grid) = s.split_once('\n').ok_or(GridError::EmptyGrid)?;
+        let label = label.trim().trim_end_matches(':').to_string();
+        Ok((label, Grid::from_str(grid)?))
+    }
+
+    /// Converts a grid of decimal digit characters into their values, or `None` if any
+    /// cell isn't a digit.
+    pub fn as_digits(&self) -> Option<Grid<u8>> {
+        let g = self
+            .g
+            .iter()
+            .map(|c| c.to_digit(10).map(|d| d as u8))
+            .collect::<Option<_>>()?;
+        Some(Grid::from_vals(g, self.width, self.height))
+    }
+
+    /// Parses the common "grid, blank line, then instructions" layout, returning the grid
+    /// and the untouched text after the blank line.
+    ///
+    /// The input is split with [`two_blocks`], so both follow the same blank-line rule.
+    pub fn parse_grid_and_rest(s: &str) -> Result<(Self, &str), GridError> {
+        let (grid, rest) = two_blocks(s).map_err(|_| GridError::MissingSection)?;
+        Ok((Self::from_str(grid)?, rest))
+    }
+
+    /// Reads each column top-to-bottom as a decimal number, ignoring spaces.
+    ///
+    /// Blank columns (e.g. separators) are skipped. Any other column that doesn't read
+    /// as a number is reported as [`GridError::InvalidCell`].
+    pub fn columns_as_numbers(&self) -> Result<Vec<u64>, GridError> {
+        (0..self.width)
+            .filter_map(|x| {
+                let digits = (0..self.height)
+                    .map(|y| self.g[y * self.width + x])
+                    .filter(|&c| c != ' ')
+                    .collect::<String>();
+                if digits.is_empty() {
+                    return None;
+                }
+                Some(digits.parse().map_err(|e: ParseIntError| {
+                    let bad = digits.chars().find(|c| !c.is_ascii_digit());
+                    GridError::InvalidCell {
+                        cell: bad.or(digits.chars().next()).unwrap_or(' '),
+                        message: e.to_string(),
+                    }
+                }))
+            })
+            .collect()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = GridError;
+
+    /// Flattens a list of rows into a grid, rejecting empty or ragged input.
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        let width = rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err(GridError::EmptyGrid);
+        }
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(GridError::Inconsistent);
+        }
+        let height = rows.len();
+        Self::try_from_vals(rows.into_iter().flatten().collect(), width, height)
+    }
 }
 
 impl<T> Index<Point> for Grid<T> {
@@ -208,26 +1364,7 @@ impl FromStr for Grid<char> {
     type Err = GridError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // Use a peekable iterator to avoid collecting into a Vec<&str> first
-        let mut lines = s.lines().filter(|l| !l.is_empty()).peekable();
-
-        let first_line = lines.peek().ok_or(GridError::EmptyGrid)?;
-        let width = first_line.len();
-
-        // Pre-allocate space if possible (estimation)
-        let mut g = Vec::new();
-        let mut height = 0;
-
-        for line in lines {
-            if line.len() != width {
-                return Err(GridError::Inconsistent);
-            }
-            g.extend(line.chars());
-            height += 1;
-        }
-
-        // Now height is explicitly tracked and g owns the chars
-        Ok(Self { width, height, g })
+        Self::try_parse_with(s, Ok::<char, Infallible>)
     }
 }
 
@@ -361,4 +1498,912 @@ mod tests {
         let res = Grid::<char>::from_str("\n\n");
         assert_eq!(res.err(), Some(GridError::EmptyGrid));
     }
+
+    #[test]
+    fn test_wrapping_get() {
+        let g = rect_grid();
+        // Negative indices wrap to the far edge
+        assert_eq!(g.wrapping_get(Point::new(-1, 0)), &g[Point::new(3, 0)]);
+        assert_eq!(g.wrapping_get(Point::new(0, -1)), &g[Point::new(0, 1)]);
+        assert_eq!(g.wrapping_get(Point::new(-5, -3)), &g[Point::new(3, 1)]);
+        // Over-large indices wrap back to the start
+        assert_eq!(g.wrapping_get(Point::new(4, 2)), &g[Point::new(0, 0)]);
+        assert_eq!(g.wrapping_get(Point::new(9, 5)), &g[Point::new(1, 1)]);
+        // In-range indices are unchanged
+        assert_eq!(g.wrapping_get(Point::new(2, 1)), &'g');
+    }
+
+    #[test]
+    fn test_try_from_vals() {
+        let g = Grid::try_from_vals(vec![1, 2, 3, 4, 5, 6], 3, 2).unwrap();
+        assert_eq!(g.width, 3);
+        assert_eq!(g.height, 2);
+        assert_eq!(g[Point::new(2, 1)], 6);
+
+        let res = Grid::try_from_vals(vec![1, 2, 3, 4, 5], 3, 2);
+        assert_eq!(res.err(), Some(GridError::Inconsistent));
+    }
+
+    #[test]
+    fn test_is_border() {
+        let g = Grid::new('.', 4, 3);
+        // Corners
+        assert!(g.is_border(Point::new(0, 0)));
+        assert!(g.is_border(Point::new(3, 2)));
+        // Edges
+        assert!(g.is_border(Point::new(2, 0)));
+        assert!(g.is_border(Point::new(0, 1)));
+        assert!(g.is_border(Point::new(3, 1)));
+        // Interior
+        assert!(!g.is_border(Point::new(1, 1)));
+        assert!(!g.is_border(Point::new(2, 1)));
+        // Out of bounds is not part of the border
+        assert!(!g.is_border(Point::new(-1, 0)));
+    }
+
+    #[test]
+    fn test_get_checked() {
+        let g = rect_grid();
+        assert_eq!(g.get(Point::new(1, 1)), Some(&'f'));
+        // Negative indices
+        assert_eq!(g.get(Point::new(-1, 0)), None);
+        assert_eq!(g.get(Point::new(0, -1)), None);
+        // Over-range indices
+        assert_eq!(g.get(Point::new(4, 0)), None);
+        assert_eq!(g.get(Point::new(0, 2)), None);
+    }
+
+    #[test]
+    fn test_try_parse_with() {
+        let to_digit = |c: char| c.to_digit(10).ok_or("not a digit");
+
+        let g = Grid::try_parse_with("12\n34", to_digit).unwrap();
+        assert_eq!(g.g, vec![1, 2, 3, 4]);
+
+        let res = Grid::try_parse_with("12\n3x", to_digit);
+        assert_eq!(
+            res.err(),
+            Some(GridError::InvalidCell {
+                cell: 'x',
+                message: "not a digit".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_crop() {
+        let g = rect_grid();
+        let cropped = g.crop(Point::new(1, 0), 2, 2).unwrap();
+        assert_eq!(cropped.to_string(), "bc\nfg\n");
+        assert_eq!(g.crop(Point::new(0, 0), 4, 2).unwrap(), g);
+
+        assert!(g.crop(Point::new(3, 0), 2, 1).is_none());
+        assert!(g.crop(Point::new(0, 1), 1, 2).is_none());
+        assert!(g.crop(Point::new(-1, 0), 1, 1).is_none());
+        assert!(g.crop(Point::new(1, 0), usize::MAX, 1).is_none());
+        assert!(g.crop(Point::new(0, 1), 1, usize::MAX).is_none());
+    }
+
+    #[test]
+    fn test_sparse_round_trip() {
+        let dense = Grid::from_vals(vec![true, false, false, false, true, true], 3, 2);
+        let sparse = dense.to_sparse();
+        assert_eq!(
+            sparse,
+            HashSet::from([Point::new(0, 0), Point::new(1, 1), Point::new(2, 1)])
+        );
+        assert_eq!(Grid::from_sparse(&sparse, 3, 2), dense);
+    }
+
+    #[test]
+    fn test_neighbor_values() {
+        let g = rect_grid();
+        let p = Point::new(1, 0);
+
+        let cardinal: Vec<_> = g.cardinal_neighbor_values(p).collect();
+        assert_eq!(cardinal.len(), 3);
+        assert!(cardinal.iter().all(|&(pos, &c)| g[pos] == c));
+
+        let all: Vec<_> = g.all_neighbor_values(p).collect();
+        assert_eq!(all.len(), 5);
+        assert!(all.iter().all(|&(pos, &c)| g[pos] == c));
+    }
+
+    #[test]
+    fn test_render() {
+        let g: Grid<u8> = Grid::from_vals(vec![0, 1, 2, 7, 8, 9], 3, 2);
+        let rendered = g.render(|&d| char::from_digit(d as u32, 10).unwrap());
+        assert_eq!(rendered, "012\n789\n");
+        assert_eq!(rendered, g.to_string());
+
+        assert_eq!(Grid::new('.', 0, 3).render(|&c| c), "");
+    }
+
+    #[test]
+    fn test_from_lines_padded() {
+        let g = Grid::from_lines_padded("ab\nabcd\n\nabc".lines(), '.');
+        assert_eq!((g.width, g.height), (4, 4));
+        assert_eq!(g.to_string(), "ab..\nabcd\n....\nabc.\n");
+    }
+
+    #[test]
+    fn test_ray_diagonal_from_corner() {
+        let g = Grid::new(0, 3, 4);
+        let ray: Vec<_> = g.ray(Point::new(0, 0), Point::DOWN + Point::RIGHT).collect();
+        assert_eq!(ray, vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)]);
+
+        assert_eq!(g.ray(Point::new(-1, 0), Point::RIGHT).count(), 0);
+    }
+
+    #[test]
+    fn test_rect_sum_matches_brute_force() {
+        // Simple LCG so the test is deterministic without a rand dependency
+        let mut seed: u32 = 12345;
+        let vals = (0..7 * 5)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (seed >> 16) as u8 % 10
+            })
+            .collect();
+        let g: Grid<u8> = Grid::from_vals(vals, 7, 5);
+        let psum = g.prefix_sum();
+
+        for (y1, y2) in (0..5).flat_map(|a| (a..5).map(move |b| (a, b))) {
+            for (x1, x2) in (0..7).flat_map(|a| (a..7).map(move |b| (a, b))) {
+                let brute: u64 = (y1..=y2)
+                    .flat_map(|y| (x1..=x2).map(move |x| Point::new(x, y)))
+                    .map(|p| g[p] as u64)
+                    .sum();
+                let fast = psum.rect_sum(Point::new(x1, y1), Point::new(x2, y2));
+                assert_eq!(fast, brute);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inside_concave_polygon() {
+        // The interior cell (1, 3) sits left of a horizontal run whose middle cells
+        // touch another stretch of boundary above it.
+        let g = Grid::from_str(
+            "####.####\n\
+             #..#.#..#\n\
+             #..###..#\n\
+             #.#######\n\
+             #.#......\n\
+             #.#######\n\
+             #.......#\n\
+             #########",
+        )
+        .unwrap();
+        let is_wall = |&c: &char| c == '#';
+
+        assert!(g.is_inside_polygon(Point::new(1, 3), is_wall));
+        assert!(g.is_inside_polygon(Point::new(1, 1), is_wall));
+        assert!(g.is_inside_polygon(Point::new(4, 6), is_wall));
+        assert!(g.is_inside_polygon(Point::new(7, 1), is_wall));
+        assert!(!g.is_inside_polygon(Point::new(4, 1), is_wall));
+        assert!(!g.is_inside_polygon(Point::new(5, 4), is_wall));
+        assert!(!g.is_inside_polygon(Point::new(0, 0), is_wall));
+    }
+
+    #[test]
+    fn test_flood_fill_if_threshold() {
+        #[rustfmt::skip]
+        let heights = vec![
+            1, 2, 9, 1,
+            3, 4, 9, 2,
+            9, 9, 9, 3,
+        ];
+        let mut g = Grid::from_vals(heights, 4, 3);
+
+        // Everything below 5 that is connected to the top-left corner
+        let filled = g.flood_fill_if(Point::new(0, 0), 0, |&h| h < 5);
+        assert_eq!(filled, 4);
+        assert_eq!(g.g, vec![0, 0, 9, 1, 0, 0, 9, 2, 9, 9, 9, 3]);
+
+        assert_eq!(g.flood_fill_if(Point::new(2, 0), 0, |&h| h < 5), 0);
+    }
+
+    #[test]
+    fn test_columns_as_numbers() {
+        let g = Grid::from_lines_padded(["1 4 ", "23 5", " 6  "], ' ');
+        assert_eq!(g.columns_as_numbers(), Ok(vec![12, 36, 4, 5]));
+
+        let bad = Grid::from_lines_padded(["1 4", "2x "], ' ');
+        assert!(matches!(
+            bad.columns_as_numbers(),
+            Err(GridError::InvalidCell { cell: 'x', .. })
+        ));
+    }
+
+    #[test]
+    fn test_bfs_multi_source() {
+        let g = Grid::from_str("....#\n..#..\n.....").unwrap();
+        let sources = [Point::new(0, 0), Point::new(4, 2)];
+        let dist = g.bfs_multi_source(&sources, |&c| c != '#');
+
+        assert_eq!(dist[Point::new(0, 0)], Some(0));
+        assert_eq!(dist[Point::new(4, 2)], Some(0));
+        assert_eq!(dist[Point::new(1, 1)], Some(2));
+        assert_eq!(dist[Point::new(3, 0)], Some(3));
+        assert_eq!(dist[Point::new(4, 1)], Some(1));
+        assert_eq!(dist[Point::new(2, 2)], Some(2));
+        assert_eq!(dist[Point::new(2, 1)], None);
+        assert_eq!(dist[Point::new(4, 0)], None);
+    }
+
+    #[test]
+    fn test_label_components() {
+        let g = Grid::from_str("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let (labels, count) = g.label_components();
+
+        assert_eq!(count, 5);
+        assert_eq!(labels.g.iter().collect::<HashSet<_>>().len(), 5);
+        assert_eq!(labels[Point::new(0, 1)], labels[Point::new(1, 2)]);
+        assert_eq!(labels[Point::new(2, 1)], labels[Point::new(3, 3)]);
+        assert_ne!(labels[Point::new(3, 1)], labels[Point::new(2, 1)]);
+    }
+
+    #[test]
+    fn test_region_perimeter_and_sides() {
+        let g = Grid::from_str("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let region_c = [
+            Point::new(2, 1),
+            Point::new(2, 2),
+            Point::new(3, 2),
+            Point::new(3, 3),
+        ];
+        assert_eq!(g.region_perimeter(&region_c), 10);
+        assert_eq!(g.region_sides(&region_c), 8);
+
+        let region_a: Vec<_> = (0..4).map(|x| Point::new(x, 0)).collect();
+        assert_eq!(g.region_perimeter(&region_a), 10);
+        assert_eq!(g.region_sides(&region_a), 4);
+    }
+
+    #[test]
+    fn test_trace_with_turns() {
+        let g = Grid::from_str(".#..\n...#\n....").unwrap();
+        let is_wall = |_, &c: &char| c == '#';
+
+        let exited = g.trace_with_turns(Point::new(1, 2), Point::UP, is_wall);
+        let expected = [(1, 2), (1, 1), (2, 1), (2, 2)].map(|(x, y)| Point::new(x, y));
+        assert_eq!(exited, TraceResult::Exited(HashSet::from(expected)));
+
+        let boxed = Grid::from_str(".#..\n...#\n#...\n..#.").unwrap();
+        let looped = boxed.trace_with_turns(Point::new(1, 1), Point::UP, is_wall);
+        assert_eq!(looped, TraceResult::Loop);
+
+        // An extra obstacle given by position turns the walker back down early
+        let extra = Point::new(2, 1);
+        let detour =
+            g.trace_with_turns(Point::new(1, 2), Point::UP, |p, &c| c == '#' || p == extra);
+        let expected = [(1, 2), (1, 1)].map(|(x, y)| Point::new(x, y));
+        assert_eq!(detour, TraceResult::Exited(HashSet::from(expected)));
+    }
+
+    #[test]
+    fn test_insert_row_and_col() {
+        let mut g = rect_grid();
+        g.insert_row(1, '.');
+        assert_eq!((g.width, g.height), (4, 3));
+        assert_eq!(g.to_string(), "abcd\n....\nefgh\n");
+
+        g.insert_col(2, '|');
+        assert_eq!((g.width, g.height), (5, 3));
+        assert_eq!(g.to_string(), "ab|cd\n..|..\nef|gh\n");
+
+        g.insert_col(5, '>');
+        g.insert_row(3, '_');
+        assert_eq!(g.to_string(), "ab|cd>\n..|..>\nef|gh>\n______\n");
+    }
+
+    #[test]
+    fn test_weighted_dijkstra() {
+        #[rustfmt::skip]
+        let risk = vec![
+            1, 1, 6, 3,
+            1, 3, 8, 1,
+            2, 1, 3, 6,
+            3, 6, 9, 4,
+        ];
+        let g = Grid::from_vals(risk, 4, 4);
+        // Down, down, right, right, right, down: 1+2+1+3+6+4 = 17
+        // versus over the top 1+6+3+1+6+4 = 21
+        assert_eq!(g.weighted_dijkstra(Point::new(0, 0), Point::new(3, 3)), Some(17));
+        assert_eq!(g.weighted_dijkstra(Point::new(0, 0), Point::new(0, 0)), Some(0));
+        assert_eq!(g.weighted_dijkstra(Point::new(0, 0), Point::new(4, 0)), None);
+    }
+
+    #[test]
+    fn test_parse_grid_and_rest() {
+        let input = "\
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<";
+        let (g, moves) = Grid::parse_grid_and_rest(input).unwrap();
+        assert_eq!((g.width, g.height), (8, 8));
+        assert_eq!(g.find_pos(|&c| c == '@'), Some(Point::new(2, 2)));
+        assert_eq!(moves, "<^^>>>vv<v>>v<<");
+
+        assert_eq!(
+            Grid::parse_grid_and_rest("##\n##").err(),
+            Some(GridError::MissingSection)
+        );
+    }
+
+    #[test]
+    fn test_push_boxes() {
+        let mut g = Grid::from_str(
+            "########\n#..O.O.#\n##@.O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########",
+        )
+        .unwrap();
+        let mut robot = g.find_pos(|&c| c == '@').unwrap();
+        for m in "<^^>>>vv<v>>v<<".chars() {
+            let dir = match m {
+                '^' => Point::UP,
+                'v' => Point::DOWN,
+                '<' => Point::LEFT,
+                _ => Point::RIGHT,
+            };
+            robot = g.push(robot, dir, |&c| c == '#', |&c| c == 'O', '.');
+        }
+
+        assert_eq!(robot, Point::new(4, 4));
+        assert_eq!(
+            g.to_string(),
+            "########\n#....OO#\n##.....#\n#.....O#\n#.#O@..#\n#...O..#\n#...O..#\n########\n"
+        );
+        let gps: i32 = g
+            .all_positions(|&c| c == 'O')
+            .map(|p| 100 * p.y + p.x)
+            .sum();
+        assert_eq!(gps, 2028);
+    }
+
+    #[test]
+    fn test_neighbors_with_wrap_flag() {
+        let g = Grid::new(0, 3, 3);
+
+        let corner: Vec<_> = g.neighbors_with_wrap_flag(Point::new(0, 0)).collect();
+        assert!(corner.contains(&(Point::new(0, 2), true)));
+        assert!(corner.contains(&(Point::new(2, 0), true)));
+        assert!(corner.contains(&(Point::new(1, 0), false)));
+        assert!(corner.contains(&(Point::new(0, 1), false)));
+
+        assert!(
+            g.neighbors_with_wrap_flag(Point::new(1, 1))
+                .all(|(_, wrapped)| !wrapped)
+        );
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let g = Grid::from_vals(vec![3, -1, 7, 0, 2, 7], 3, 2);
+        assert_eq!(g.sum(), 18);
+        assert_eq!(g.max(), Some(&7));
+        assert_eq!(g.min(), Some(&-1));
+
+        let empty: Grid<u8> = Grid::from_vals(vec![], 0, 0);
+        assert_eq!(empty.sum(), 0);
+        assert_eq!(empty.max(), None);
+    }
+
+    #[test]
+    fn test_iter_cells() {
+        let g = rect_grid();
+        assert_eq!(g.iter().collect::<String>(), "abcdefgh");
+
+        let mut cells = Vec::new();
+        for &c in &g {
+            cells.push(c);
+        }
+        assert_eq!(cells, vec!['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h']);
+    }
+
+    #[test]
+    fn test_index_by_value() {
+        let g = Grid::from_str("a.b\n.a.\nb.a").unwrap();
+        let index = g.index_by_value();
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(
+            index[&'a'],
+            vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)]
+        );
+        assert_eq!(index[&'b'], vec![Point::new(2, 0), Point::new(0, 2)]);
+        assert_eq!(index[&'.'].len(), 4);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let t = rect_grid().transpose();
+        assert_eq!((t.width, t.height), (2, 4));
+        assert_eq!(t.to_string(), "ae\nbf\ncg\ndh\n");
+    }
+
+    #[test]
+    fn test_consuming_transforms_match_cloning() {
+        let g = rect_grid();
+        assert_eq!(g.clone().into_transpose(), g.transpose());
+        assert_eq!(g.clone().into_rotate_right(), g.rotate_right());
+        assert_eq!(g.clone().into_flip_lr(), g.flip_lr());
+    }
+
+    #[test]
+    fn test_eq_ignoring() {
+        let a = rect_grid();
+        let mut b = rect_grid();
+        b[Point::new(1, 0)] = '@';
+        b[Point::new(3, 1)] = '@';
+        assert!(!a.eq_ignoring(&b, |_| false));
+        assert!(a.eq_ignoring(&b, |p| p == Point::new(1, 0) || p == Point::new(3, 1)));
+        assert!(!a.eq_ignoring(&b, |p| p == Point::new(1, 0)));
+        assert!(!a.eq_ignoring(&a.transpose(), |_| true));
+    }
+
+    #[test]
+    fn test_try_from_rows() {
+        let rows = vec![vec!['a', 'b', 'c', 'd'], vec!['e', 'f', 'g', 'h']];
+        assert_eq!(Grid::try_from(rows), Ok(rect_grid()));
+
+        let ragged = vec![vec!['a', 'b'], vec!['c']];
+        assert_eq!(Grid::try_from(ragged), Err(GridError::Inconsistent));
+        assert_eq!(
+            Grid::<char>::try_from(Vec::new()),
+            Err(GridError::EmptyGrid)
+        );
+    }
+
+    #[test]
+    fn test_line() {
+        let g = rect_grid();
+        assert!(g.line(Axis::Row, 0).eq(g.row(0)));
+        assert_eq!(g.row(1).collect::<String>(), "efgh");
+        assert_eq!(g.line(Axis::Col, 2).collect::<String>(), "cg");
+        assert!(g.col(3).eq(g.transpose().row(3)));
+    }
+
+    #[test]
+    fn test_region_counts() {
+        let g = Grid::from_str("AAB\nBBB\nAAA\nCBA").unwrap();
+        let counts = g.region_counts();
+        assert_eq!(counts[&'A'], 2);
+        assert_eq!(counts[&'B'], 2);
+        assert_eq!(counts[&'C'], 1);
+        assert_eq!(counts.len(), 3);
+    }
+
+    #[test]
+    fn test_has_run() {
+        let g = Grid::from_str("#.##\n###.\n##.#").unwrap();
+        assert!(g.has_run(Axis::Row, 3, |&c| c == '#'));
+        assert!(!g.has_run(Axis::Row, 4, |&c| c == '#'));
+        assert!(g.has_run(Axis::Col, 3, |&c| c == '#'));
+        assert!(!g.has_run(Axis::Col, 2, |&c| c == '.'));
+    }
+
+    #[test]
+    fn test_as_digits() {
+        let g = Grid::from_str("012\n789").unwrap().as_digits().unwrap();
+        assert_eq!(g.g, vec![0, 1, 2, 7, 8, 9]);
+        assert_eq!((g.width, g.height), (3, 2));
+        assert_eq!(Grid::from_str("01\n2a").unwrap().as_digits(), None);
+    }
+
+    #[test]
+    fn test_weighted_cardinal_neighbors() {
+        let g = Grid::from_str("19\n35").unwrap().as_digits().unwrap();
+        let mut got: Vec<_> = g
+            .weighted_cardinal_neighbors(Point::new(0, 0), |&d| usize::from(d) * 10)
+            .collect();
+        got.sort();
+        assert_eq!(got, vec![(Point::new(0, 1), 30), (Point::new(1, 0), 90)]);
+    }
+
+    #[test]
+    fn test_get_or() {
+        let g = rect_grid();
+        assert_eq!(g.get_or(Point::new(1, 1), &'.'), &'f');
+        assert_eq!(g.get_or(Point::new(-1, 0), &'.'), &'.');
+        assert_eq!(g.get_or(Point::new(4, 1), &'.'), &'.');
+        assert_eq!(g.get_or(Point::new(0, 2), &'.'), &'.');
+    }
+
+    #[test]
+    fn test_corner_context() {
+        let g = rect_grid();
+        let [tl, tr, bl, br] = g.corner_context(Point::new(1, 0));
+        assert_eq!(tl, (None, Some(&'a'), None));
+        assert_eq!(tr, (None, Some(&'c'), None));
+        assert_eq!(bl, (Some(&'f'), Some(&'a'), Some(&'e')));
+        assert_eq!(br, (Some(&'f'), Some(&'c'), Some(&'g')));
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut g = rect_grid();
+        g.swap(Point::new(0, 0), Point::new(3, 1));
+        assert_eq!(g.to_string(), "hbcd\nefga\n");
+        assert!(g.try_swap(Point::new(0, 0), Point::new(3, 1)));
+        assert_eq!(g, rect_grid());
+    }
+
+    #[test]
+    fn test_try_swap_out_of_bounds() {
+        let mut g = rect_grid();
+        assert!(!g.try_swap(Point::new(0, 0), Point::new(4, 0)));
+        assert!(!g.try_swap(Point::new(0, -1), Point::new(1, 1)));
+        assert_eq!(g, rect_grid());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot swap")]
+    fn test_swap_out_of_bounds_panics() {
+        rect_grid().swap(Point::new(0, 0), Point::new(0, 2));
+    }
+
+    #[test]
+    fn test_path_cost() {
+        let g = Grid::from_vals((1..=9).collect::<Vec<u32>>(), 3, 3);
+        let diagonal: Vec<_> = (0..3).map(|i| Point::new(i, i)).collect();
+        assert_eq!(g.path_cost(&diagonal), 1 + 5 + 9);
+        assert_eq!(g.path_cost(&[]), 0);
+    }
+
+    #[test]
+    fn test_to_rows_round_trip() {
+        let g = rect_grid();
+        let rows = g.to_rows();
+        assert_eq!(
+            rows,
+            vec![vec!['a', 'b', 'c', 'd'], vec!['e', 'f', 'g', 'h']]
+        );
+        assert_eq!(Grid::try_from(rows), Ok(g));
+    }
+
+    #[test]
+    fn test_direction8() {
+        let deltas: Vec<_> = Direction8::ALL.iter().map(|d| d.to_delta()).collect();
+        let expected = [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ];
+        assert_eq!(deltas, expected.map(|(x, y)| Point::new(x, y)));
+        for d in Direction8::ALL {
+            assert_eq!(d.opposite().to_delta(), d.to_delta() * -1);
+            assert_eq!(d.opposite().opposite(), d);
+        }
+    }
+
+    #[test]
+    fn test_row_slice() {
+        let g = rect_grid();
+        assert_eq!(g.row_slice(1), g.row(1).copied().collect::<Vec<_>>());
+        assert_eq!(g.row_slice(0).windows(2).count(), 3);
+    }
+
+    #[test]
+    fn test_dfs() {
+        let g = Grid::from_str("..#.\n.##.\n....\n###.").unwrap();
+        let mut order = Vec::new();
+        g.dfs(Point::new(0, 0), |&c| c == '.', |p| order.push(p));
+        assert_eq!(order.len(), g.iter().filter(|&&c| c == '.').count());
+        assert_eq!(order[0], Point::new(0, 0));
+        assert_eq!(order.iter().collect::<HashSet<_>>().len(), order.len());
+
+        let mut visited = 0;
+        g.dfs(Point::new(2, 0), |&c| c == '.', |_| visited += 1);
+        assert_eq!(visited, 0);
+    }
+
+    #[test]
+    fn test_from_delimited() {
+        let g = Grid::from_delimited("a,bb,ccc\ndd, e,f\n", ',', str::to_string).unwrap();
+        assert_eq!((g.width, g.height), (3, 2));
+        assert_eq!(g[Point::new(2, 0)], "ccc");
+        assert_eq!(g[Point::new(1, 1)], "e");
+
+        let nums = Grid::from_delimited("1;20\n300;4", ';', |t| t.parse::<u32>().unwrap()).unwrap();
+        assert_eq!(nums.sum(), 325);
+
+        assert_eq!(
+            Grid::from_delimited("a,b\nc", ',', str::to_string),
+            Err(GridError::Inconsistent)
+        );
+        assert_eq!(
+            Grid::from_delimited("", ',', str::to_string),
+            Err(GridError::EmptyGrid)
+        );
+    }
+
+    #[test]
+    fn test_parity() {
+        let g = Grid::new('.', 3, 3);
+        assert_eq!(g.positions_with_parity(true).count(), 5);
+        assert_eq!(g.positions_with_parity(false).count(), 4);
+        assert!(g.positions_with_parity(true).all(|p| (p.x + p.y) % 2 == 0));
+        assert!(Grid::<char>::parity(Point::new(-1, -1)));
+        assert!(!Grid::<char>::parity(Point::new(-1, 0)));
+    }
+
+    #[test]
+    fn test_centered_in() {
+        let g = Grid::from_str("ab\ncd").unwrap();
+        let centered = g.centered_in(4, 4, '.').unwrap();
+        assert_eq!(centered.to_string(), "....\n.ab.\n.cd.\n....\n");
+        assert_eq!(centered.crop(Point::new(1, 1), 2, 2), Some(g.clone()));
+
+        assert_eq!(
+            g.centered_in(5, 2, '.').unwrap().to_string(),
+            ".ab..\n.cd..\n"
+        );
+        assert_eq!(g.centered_in(2, 2, '.'), Some(g.clone()));
+        assert!(g.centered_in(1, 4, '.').is_none());
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let g = Grid::new('.', 3, 3);
+        let falling: Vec<_> = g.diagonals(Diagonal::Falling).collect();
+        assert_eq!(falling.len(), 5);
+        assert_eq!(falling[0], vec![Point::new(0, 2)]);
+        assert_eq!(
+            falling[2],
+            vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)]
+        );
+        assert_eq!(falling[4], vec![Point::new(2, 0)]);
+
+        let rising: Vec<_> = g.diagonals(Diagonal::Rising).collect();
+        assert_eq!(
+            rising[2],
+            vec![Point::new(0, 2), Point::new(1, 1), Point::new(2, 0)]
+        );
+
+        // Both families cover every cell exactly once
+        let r = rect_grid();
+        for family in [Diagonal::Falling, Diagonal::Rising] {
+            let cells: Vec<_> = r.diagonals(family).flatten().collect();
+            assert_eq!(cells.len(), 8);
+            assert_eq!(cells.iter().collect::<HashSet<_>>().len(), 8);
+        }
+    }
+
+    #[test]
+    fn test_fill_rect() {
+        let mut g = Grid::new('.', 4, 3);
+        g.fill_rect(Point::new(1, 1), 2, 2, '#');
+        assert_eq!(g.to_string(), "....\n.##.\n.##.\n");
+
+        // Clipped at the edges
+        let mut g = Grid::new('.', 3, 3);
+        g.fill_rect(Point::new(-1, 2), 3, 5, '#');
+        assert_eq!(g.to_string(), "...\n...\n##.\n");
+        g.fill_rect(Point::new(5, 0), 2, 2, '#');
+        assert_eq!(g.iter().filter(|&&c| c == '#').count(), 2);
+    }
+
+    #[test]
+    fn test_unpad() {
+        let g = rect_grid();
+        assert_eq!(g.expand('.').unpad(1), Some(g.clone()));
+        assert_eq!(g.expand('.').expand('#').unpad(2), Some(g.clone()));
+        assert_eq!(g.unpad(0), Some(g.clone()));
+
+        let inner = Grid::from_str("abc\ndef\nghi").unwrap().unpad(1).unwrap();
+        assert_eq!(inner.to_string(), "e\n");
+        assert_eq!(g.unpad(g.width.max(g.height)), None);
+        assert_eq!(Grid::new('.', 4, 4).unpad(2), None);
+    }
+
+    #[test]
+    fn test_flood_fill_distances() {
+        let room = Grid::new('.', 5, 4);
+        let seed = Point::new(1, 1);
+        let dist = room.flood_fill_distances(seed, |&c| c == '#');
+        assert_eq!(dist.len(), 20);
+        let (&far, &d) = dist.iter().max_by_key(|&(_, d)| d).unwrap();
+        assert_eq!(far, Point::new(4, 3));
+        assert_eq!(d, seed.manhattan_distance(&far) as usize);
+
+        let walled = Grid::from_str("..#\n#.#\n..#").unwrap();
+        let dist = walled.flood_fill_distances(Point::new(0, 0), |&c| c == '#');
+        assert_eq!(dist.len(), 5);
+        assert_eq!(dist.get(&Point::new(0, 2)), Some(&4));
+        assert!(
+            walled
+                .flood_fill_distances(Point::new(2, 0), |&c| c == '#')
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_cardinal_neighbor_dirs() {
+        let g = Grid::new('.', 3, 3);
+        let centre = Point::new(1, 1);
+        let dirs: Vec<_> = g.cardinal_neighbor_dirs(centre).collect();
+        assert_eq!(dirs.len(), 4);
+        assert!(dirs.iter().all(|&(dir, pos)| pos - centre == dir));
+        assert!(dirs.contains(&(Point::UP, Point::new(1, 0))));
+        assert!(dirs.contains(&(Point::RIGHT, Point::new(2, 1))));
+
+        let corner: Vec<_> = g
+            .cardinal_neighbor_dirs(Point::new(0, 0))
+            .map(|(d, _)| d)
+            .collect();
+        assert_eq!(corner, vec![Point::DOWN, Point::RIGHT]);
+    }
+
+    #[test]
+    fn test_reachable_within() {
+        let open = Grid::new('.', 7, 7);
+        let centre = Point::new(3, 3);
+        let diamond = open.reachable_within(centre, 2, |&c| c == '.');
+        assert_eq!(diamond.len(), 13);
+        assert!(diamond.iter().all(|p| p.manhattan_distance(&centre) <= 2));
+        assert_eq!(open.reachable_within(centre, 0, |&c| c == '.').len(), 1);
+
+        let walled = Grid::from_str("..#..\n..#..").unwrap();
+        assert_eq!(
+            walled
+                .reachable_within(Point::new(0, 0), 10, |&c| c == '.')
+                .len(),
+            4
+        );
+        assert!(
+            walled
+                .reachable_within(Point::new(2, 0), 3, |&c| c == '.')
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_par_for_each_start() {
+        let g = Grid::from_str("a.b\n.c.\nd.e").unwrap();
+        let starts: Vec<_> = g.all_positions(|&c| c != '.').collect();
+        let reach = |p: Point| g.reachable_within(p, 1, |_| true).len();
+
+        let parallel = g.par_for_each_start(starts.iter().copied(), reach);
+        let sequential: Vec<_> = starts.iter().map(|&p| reach(p)).collect();
+        assert_eq!(parallel, sequential);
+        assert_eq!(parallel, vec![3, 3, 5, 3, 3]);
+    }
+
+    #[test]
+    fn test_iterate_until_cycle() {
+        // Spins the row one cell right, ageing 'a' into 'b' on the way, so one transient
+        // state precedes a cycle of period 4
+        let step = |g: &Grid<char>| {
+            let mut next = g.clone();
+            for x in 0..g.width {
+                let c = g[Point::new(x as i32, 0)];
+                next[Point::new(((x + 1) % g.width) as i32, 0)] = if c == 'a' { 'b' } else { c };
+            }
+            next
+        };
+        let start = Grid::from_str("a#..").unwrap();
+        let brute_force = |n: usize| (0..n).fold(start.clone(), |g, _| step(&g));
+
+        for target in [0, 1, 2, 5, 8, 1001] {
+            assert_eq!(start.iterate_until_cycle(step, target), brute_force(target));
+        }
+        // 1_000_000_001 is 1 past a multiple of the period, like step 5 is
+        assert_eq!(
+            start.iterate_until_cycle(step, 1_000_000_001),
+            brute_force(5)
+        );
+    }
+
+    #[test]
+    fn test_neighbors_with_hex() {
+        let g = Grid::new('.', 3, 3);
+        let hex: Vec<_> = g.neighbors_with(Point::new(1, 1), &HEX_DELTAS).collect();
+        assert_eq!(hex.len(), 6);
+        assert!(!hex.contains(&Point::new(0, 0)));
+        assert!(!hex.contains(&Point::new(2, 2)));
+
+        // The corner at (0, 0) keeps only its (1, 0) and (0, 1) neighbours
+        assert_eq!(g.neighbors_with(Point::new(0, 0), &HEX_DELTAS).count(), 2);
+        assert_eq!(
+            g.neighbors_with(Point::new(1, 1), &[Point::UP, Point::LEFT])
+                .collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_corners_at() {
+        let plus = Grid::from_str(".#.\n###\n.#.").unwrap();
+        // The tip of an arm has two outer corners
+        assert_eq!(plus.corners_at(Point::new(1, 0), &'#'), 2);
+        // The centre has an inner corner in each quadrant
+        assert_eq!(plus.corners_at(Point::new(1, 1), &'#'), 4);
+        assert_eq!(
+            plus.region_sides(&plus.all_positions(|&c| c == '#').collect::<Vec<_>>()),
+            12
+        );
+
+        let lone = Grid::from_str("...\n.#.\n...").unwrap();
+        assert_eq!(lone.corners_at(Point::new(1, 1), &'#'), 4);
+        // The ring's corner cell touches its outer corner and the hole's inner corner
+        assert_eq!(lone.corners_at(Point::new(0, 0), &'.'), 2);
+    }
+
+    #[test]
+    fn test_count_monotone_paths_tachyon_manifold() {
+        // 2025 day 7: a beam falls straight down until a splitter directly below sends it
+        // diagonally to either side
+        let manifold = Grid::from_str(
+            ".......S.......\n...............\n.......^.......\n...............\n\
+             ......^.^......\n...............\n.....^.^.^.....\n...............\n\
+             ....^.^...^....\n...............\n...^.^...^.^...\n...............\n\
+             ..^...^.....^..\n...............\n.^.^.^.^.^...^.\n...............",
+        )
+        .unwrap();
+        let start = manifold.find_pos(|&c| c == 'S').unwrap();
+        let paths = manifold.count_monotone_paths(&[start], |from, to| {
+            let splits = manifold[from + Point::DOWN] == '^';
+            if to.x == from.x { !splits } else { splits }
+        });
+        let timelines: u64 = paths.row(manifold.height - 1).sum();
+        assert_eq!(timelines, 40);
+    }
+
+    #[test]
+    fn test_count_monotone_paths_open_grid() {
+        let g = Grid::new('.', 3, 3);
+        let paths = g.count_monotone_paths(&[Point::new(1, 0)], |_, _| true);
+        assert_eq!(paths.g, vec![0, 1, 0, 1, 1, 1, 2, 3, 2]);
+        assert_eq!(
+            g.count_monotone_paths(&[Point::new(5, 0)], |_, _| true).g,
+            vec![0; 9]
+        );
+    }
+
+    #[test]
+    fn test_from_labeled_block() {
+        let (label, grid) = Grid::from_labeled_block("4:\n###\n#..\n###\n#..").unwrap();
+        assert_eq!(label, "4");
+        assert_eq!((grid.width, grid.height), (3, 4));
+        assert_eq!(grid[Point::new(0, 3)], '#');
+
+        assert_eq!(Grid::from_labeled_block("4:"), Err(GridError::EmptyGrid));
+        assert_eq!(Grid::from_labeled_block("4:\n"), Err(GridError::EmptyGrid));
+        assert_eq!(
+            Grid::from_labeled_block("4:\n##\n#"),
+            Err(GridError::Inconsistent)
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_from() {
+        // A wall forces the far column to be reached around the bottom, through the
+        // expensive '9'
+        let g = Grid::from_str("1#1\n1#1\n191").unwrap();
+        let dist = g.dijkstra_from(Point::new(0, 0), |&c| match c {
+            '#' => None,
+            c => c.to_digit(10).map(|d| d as usize),
+        });
+        assert_eq!(dist[Point::new(1, 0)], None);
+        assert_eq!(dist[Point::new(1, 1)], None);
+        assert_eq!(dist[Point::new(0, 2)], Some(2));
+        assert_eq!(dist[Point::new(1, 2)], Some(11));
+        assert_eq!(dist[Point::new(2, 0)], Some(14));
+
+        let walled = g.dijkstra_from(Point::new(1, 0), |&c| (c != '#').then_some(1));
+        assert!(walled.iter().all(Option::is_none));
+    }
 }
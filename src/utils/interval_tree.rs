@@ -3,10 +3,13 @@
 //! An augmented interval tree for $O(\log N)$ range-overlap and point queries.
 //! Includes utilities for merging, subtracting, and deleting intervals.
 
-use std::cmp::max;
-use std::ops::RangeInclusive;
+use std::cmp::{max, min};
+use std::collections::BTreeSet;
+use num::One;
+use std::ops::{Add, RangeInclusive, Sub};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Intervals order by `low`, then by `high`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Interval<T> {
     pub low: T,
     pub high: T,
@@ -27,6 +30,28 @@ impl<T: Ord + Copy> Interval<T> {
         p >= self.low && p <= self.high
     }
 
+    /// Checks if `other` lies entirely within this interval.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.low <= other.low && other.high <= self.high
+    }
+
+    /// Returns the part of this interval that lies within `bounds`, or `None` if it lies
+    /// entirely outside.
+    pub fn clamp(self, bounds: Self) -> Option<Self> {
+        self.overlaps(&bounds)
+            .then(|| Self::new(max(self.low, bounds.low), min(self.high, bounds.high)))
+    }
+
+    /// Returns the single interval covering both, if they overlap or touch at an endpoint.
+    ///
+    /// Uses the same rule as [`Interval::merge_all`]; disjoint intervals with a gap give `None`.
+    /// Integer-adjacent intervals that share no endpoint, such as `10..=20` and `21..=30`,
+    /// count as disjoint and are not merged.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.overlaps(other)
+            .then(|| Self::new(min(self.low, other.low), max(self.high, other.high)))
+    }
+
     /// Returns the difference (self - other).
     /// Note: This is a discrete difference. For AoC puzzles (i32/usize),
     /// you may need to adjust the boundaries by +/- 1 depending on whether
@@ -69,6 +94,15 @@ impl<T: Ord + Copy> Interval<T> {
     }
 }
 
+impl<T: Ord + Copy + Add<Output = T> + Sub<Output = T> + One> Interval<T> {
+    /// Number of integers covered by this inclusive interval, `high - low + 1`.
+    ///
+    /// Overflows when the interval spans the whole range of `T` (e.g. `0..=u64::MAX`).
+    pub fn len(&self) -> T {
+        self.high - self.low + T::one()
+    }
+}
+
 impl<T: Ord + Copy> From<RangeInclusive<T>> for Interval<T> {
     fn from(r: RangeInclusive<T>) -> Self {
         Self::new(*r.start(), *r.end())
@@ -105,11 +139,27 @@ impl<T: Ord + Copy> Node<T> {
 #[derive(Debug, Default, PartialEq)]
 pub struct IntervalTree<T> {
     root: Option<Box<Node<T>>>,
+    len: usize,
 }
 
 impl<T: Ord + Copy> IntervalTree<T> {
     pub fn new() -> Self {
-        Self { root: None }
+        Self { root: None, len: 0 }
+    }
+
+    /// Number of intervals stored in the tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes every interval from the tree.
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
     }
 
     /// Build a tree from a list of intervals, merging them first to ensure disjoint ranges.
@@ -118,9 +168,26 @@ impl<T: Ord + Copy> IntervalTree<T> {
         merged.into_iter().collect()
     }
 
+    /// Merges overlapping stored intervals, rebuilding the tree from the disjoint result.
+    ///
+    /// The in-place counterpart of [`IntervalTree::from_merged`].
+    pub fn coalesce(&mut self) {
+        let mut intervals = Vec::with_capacity(self.len);
+        Self::drain_rec(self.root.take(), &mut intervals);
+        *self = Self::from_merged(intervals);
+    }
+
+    fn drain_rec(node: Option<Box<Node<T>>>, out: &mut Vec<Interval<T>>) {
+        let Some(n) = node else { return };
+        out.push(n.interval);
+        Self::drain_rec(n.left, out);
+        Self::drain_rec(n.right, out);
+    }
+
     pub fn insert(&mut self, low: T, high: T) {
         let interval = Interval::new(low, high);
         self.root = Self::insert_rec(self.root.take(), interval);
+        self.len += 1;
     }
 
     fn insert_rec(node: Option<Box<Node<T>>>, interval: Interval<T>) -> Option<Box<Node<T>>> {
@@ -141,17 +208,27 @@ impl<T: Ord + Copy> IntervalTree<T> {
 
     /// Removes a specific interval from the tree.
     pub fn delete(&mut self, low: T, high: T) {
-        self.root = Self::delete_rec(self.root.take(), low, high);
+        let mut removed = false;
+        self.root = Self::delete_rec(self.root.take(), low, high, &mut removed);
+        if removed {
+            self.len -= 1;
+        }
     }
 
-    fn delete_rec(node: Option<Box<Node<T>>>, low: T, high: T) -> Option<Box<Node<T>>> {
+    fn delete_rec(
+        node: Option<Box<Node<T>>>,
+        low: T,
+        high: T,
+        removed: &mut bool,
+    ) -> Option<Box<Node<T>>> {
         let mut n = node?;
 
         if low < n.interval.low {
-            n.left = Self::delete_rec(n.left.take(), low, high);
+            n.left = Self::delete_rec(n.left.take(), low, high, removed);
         } else if low > n.interval.low || n.interval.high != high {
-            n.right = Self::delete_rec(n.right.take(), low, high);
+            n.right = Self::delete_rec(n.right.take(), low, high, removed);
         } else {
+            *removed = true;
             if n.left.is_none() { return n.right; }
             if n.right.is_none() { return n.left; }
 
@@ -180,21 +257,59 @@ impl<T: Ord + Copy> IntervalTree<T> {
     }
 
     pub fn find_all_overlapping(&self, query: Interval<T>) -> Vec<Interval<T>> {
+        self.overlapping(query).copied().collect()
+    }
+
+    /// Lazily yields the stored intervals overlapping `query`, in the same order as
+    /// [`IntervalTree::find_all_overlapping`].
+    pub fn overlapping(&self, query: Interval<T>) -> Overlapping<'_, T> {
+        Overlapping {
+            stack: self.root.as_deref().into_iter().collect(),
+            query,
+        }
+    }
+
+    /// Returns all intervals starting strictly after `p`, ordered by their start.
+    pub fn find_all_after(&self, p: T) -> Vec<Interval<T>> {
         let mut results = Vec::new();
-        Self::find_all_overlapping_rec(&self.root, query, &mut results);
+        Self::find_all_after_rec(&self.root, p, &mut results);
         results
     }
 
-    fn find_all_overlapping_rec(node: &Option<Box<Node<T>>>, query: Interval<T>, results: &mut Vec<Interval<T>>) {
-        let n = match node {
-            Some(n) if n.max_high >= query.low => n,
-            _ => return,
-        };
-        if n.interval.overlaps(&query) {
+    fn find_all_after_rec(node: &Option<Box<Node<T>>>, p: T, results: &mut Vec<Interval<T>>) {
+        let Some(n) = node else { return };
+        // Everything in the left subtree starts before this node, so it can only
+        // qualify if this node does.
+        if n.interval.low > p {
+            Self::find_all_after_rec(&n.left, p, results);
             results.push(n.interval);
         }
-        Self::find_all_overlapping_rec(&n.left, query, results);
-        Self::find_all_overlapping_rec(&n.right, query, results);
+        Self::find_all_after_rec(&n.right, p, results);
+    }
+}
+
+/// Iterator returned by [`IntervalTree::overlapping`].
+pub struct Overlapping<'a, T> {
+    stack: Vec<&'a Node<T>>,
+    query: Interval<T>,
+}
+
+impl<'a, T: Ord + Copy> Iterator for Overlapping<'a, T> {
+    type Item = &'a Interval<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(n) = self.stack.pop() {
+            // No interval in this subtree reaches the query
+            if n.max_high < self.query.low {
+                continue;
+            }
+            self.stack.extend(n.right.as_deref());
+            self.stack.extend(n.left.as_deref());
+            if n.interval.overlaps(&self.query) {
+                return Some(&n.interval);
+            }
+        }
+        None
     }
 }
 
@@ -208,6 +323,152 @@ impl<T: Ord + Copy> FromIterator<Interval<T>> for IntervalTree<T> {
     }
 }
 
+#[derive(Debug, PartialEq)]
+struct MapNode<T, V> {
+    interval: Interval<T>,
+    value: V,
+    max_high: T,
+    left: Option<Box<MapNode<T, V>>>,
+    right: Option<Box<MapNode<T, V>>>,
+}
+
+/// An augmented interval tree like [`IntervalTree`] that attaches a value to each stored
+/// interval, for range-to-value lookups.
+#[derive(Debug, PartialEq)]
+pub struct IntervalMap<T, V> {
+    root: Option<Box<MapNode<T, V>>>,
+    len: usize,
+}
+
+impl<T, V> Default for IntervalMap<T, V> {
+    fn default() -> Self {
+        Self { root: None, len: 0 }
+    }
+}
+
+impl<T: Ord + Copy, V> IntervalMap<T, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of entries stored in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Associates `value` with `interval`. Overlapping intervals are kept side by side.
+    pub fn insert(&mut self, interval: Interval<T>, value: V) {
+        let mut slot = &mut self.root;
+        while let Some(n) = slot {
+            n.max_high = max(n.max_high, interval.high);
+            slot = if interval.low < n.interval.low {
+                &mut n.left
+            } else {
+                &mut n.right
+            };
+        }
+        *slot = Some(Box::new(MapNode {
+            interval,
+            value,
+            max_high: interval.high,
+            left: None,
+            right: None,
+        }));
+        self.len += 1;
+    }
+
+    /// Every entry whose interval contains `p`.
+    pub fn find_at_point(&self, p: T) -> Vec<(Interval<T>, &V)> {
+        self.find_all_overlapping(Interval::new(p, p))
+    }
+
+    /// Every entry whose interval overlaps `query`.
+    pub fn find_all_overlapping(&self, query: Interval<T>) -> Vec<(Interval<T>, &V)> {
+        let mut results = Vec::new();
+        let mut stack: Vec<&MapNode<T, V>> = self.root.as_deref().into_iter().collect();
+        while let Some(n) = stack.pop() {
+            if n.max_high < query.low {
+                continue;
+            }
+            if n.interval.overlaps(&query) {
+                results.push((n.interval, &n.value));
+            }
+            stack.extend(n.right.as_deref());
+            stack.extend(n.left.as_deref());
+        }
+        results
+    }
+}
+
+impl<T: Ord + Copy, V> FromIterator<(Interval<T>, V)> for IntervalMap<T, V> {
+    fn from_iter<I: IntoIterator<Item = (Interval<T>, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (interval, value) in iter {
+            map.insert(interval, value);
+        }
+        map
+    }
+}
+
+/// An ordered set of disjoint intervals that merges overlapping or touching
+/// intervals as they are inserted.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DisjointIntervalSet<T> {
+    set: BTreeSet<Interval<T>>,
+}
+
+impl<T: Ord + Copy> DisjointIntervalSet<T> {
+    pub fn new() -> Self {
+        Self { set: BTreeSet::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Inserts `interval`, absorbing every stored interval it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let key = Interval::new(interval.high, interval.high);
+        // Stored intervals are disjoint, so both ends are sorted and the overlapping
+        // ones form a single run ending at (or just after) `key`.
+        let mut absorbed: Vec<_> = self
+            .set
+            .range(..key)
+            .rev()
+            .take_while(|iv| iv.high >= interval.low)
+            .copied()
+            .collect();
+        absorbed.extend(self.set.range(key..).next().filter(|iv| iv.low == interval.high));
+
+        let mut merged = interval;
+        for iv in absorbed {
+            self.set.remove(&iv);
+            merged = merged.union(&iv).expect("absorbed intervals overlap the insert");
+        }
+        self.set.insert(merged);
+    }
+
+    /// Checks if any stored interval contains `p`.
+    pub fn covers(&self, p: T) -> bool {
+        let key = Interval::new(p, p);
+        self.set.range(..=key).next_back().is_some_and(|iv| iv.contains(p))
+            || self.set.range(key..).next().is_some_and(|iv| iv.contains(p))
+    }
+
+    /// Stored intervals in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.set.iter()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,4 +563,178 @@ mod tests {
         assert_eq!(tree.find_at_point(0).len(), 1);
         assert_eq!(tree.find_at_point(-5).len(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_clamp() {
+        let bounds = Interval::new(0, 20);
+        assert_eq!(Interval::new(5, 15).clamp(bounds), Some(Interval::new(5, 15)));
+        assert_eq!(Interval::new(-5, 10).clamp(bounds), Some(Interval::new(0, 10)));
+        assert_eq!(Interval::new(10, 30).clamp(bounds), Some(Interval::new(10, 20)));
+        assert_eq!(Interval::new(-5, 30).clamp(bounds), Some(bounds));
+        assert_eq!(Interval::new(21, 30).clamp(bounds), None);
+        assert_eq!(Interval::new(-9, -1).clamp(bounds), None);
+    }
+
+    #[test]
+    fn test_find_all_after() {
+        let tree: IntervalTree<_> = vec![
+            Interval::new(10, 20),
+            Interval::new(5, 8),
+            Interval::new(25, 30),
+            Interval::new(15, 16),
+            Interval::new(1, 40),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            tree.find_all_after(10),
+            vec![Interval::new(15, 16), Interval::new(25, 30)]
+        );
+        assert_eq!(tree.find_all_after(0).len(), 5);
+        assert!(tree.find_all_after(25).is_empty());
+    }
+
+    #[test]
+    fn test_len_and_clear() {
+        let mut tree = IntervalTree::new();
+        assert!(tree.is_empty());
+
+        tree.insert(10, 20);
+        tree.insert(5, 8);
+        tree.insert(15, 25);
+        assert_eq!(tree.len(), 3);
+
+        tree.delete(5, 8);
+        assert_eq!(tree.len(), 2);
+        // Deleting an interval that isn't stored leaves the count alone
+        tree.delete(5, 8);
+        tree.delete(10, 21);
+        assert_eq!(tree.len(), 2);
+
+        tree.clear();
+        assert!(tree.is_empty());
+        assert!(tree.find_at_point(15).is_empty());
+    }
+
+    #[test]
+    fn test_union() {
+        let base = Interval::new(10, 20);
+        assert_eq!(base.union(&Interval::new(15, 25)), Some(Interval::new(10, 25)));
+        assert_eq!(base.union(&Interval::new(12, 18)), Some(base));
+        assert_eq!(base.union(&Interval::new(20, 30)), Some(Interval::new(10, 30)));
+        assert_eq!(base.union(&Interval::new(0, 10)), Some(Interval::new(0, 20)));
+        assert_eq!(base.union(&Interval::new(21, 30)), None);
+        assert_eq!(base.union(&Interval::new(22, 30)), None);
+    }
+
+    #[test]
+    fn test_interval_ordering() {
+        let mut ivs = vec![Interval::new(3, 4), Interval::new(1, 9), Interval::new(1, 2)];
+        ivs.sort();
+        assert_eq!(ivs, vec![Interval::new(1, 2), Interval::new(1, 9), Interval::new(3, 4)]);
+    }
+
+    #[test]
+    fn test_disjoint_interval_set() {
+        let mut set = DisjointIntervalSet::new();
+        set.insert(Interval::new(10, 15));
+        set.insert(Interval::new(1, 3));
+        set.insert(Interval::new(20, 25));
+        assert_eq!(set.len(), 3);
+
+        // Bridges [10, 15] and [20, 25], and touches [1, 3] at its end
+        set.insert(Interval::new(3, 4));
+        set.insert(Interval::new(14, 20));
+        let got: Vec<_> = set.iter().copied().collect();
+        assert_eq!(got, vec![Interval::new(1, 4), Interval::new(10, 25)]);
+
+        set.insert(Interval::new(0, 30));
+        assert_eq!(set.len(), 1);
+        assert!(set.covers(0) && set.covers(30));
+        assert!(!set.covers(31));
+    }
+
+    #[test]
+    fn test_interval_len() {
+        assert_eq!(Interval::new(10, 20).len(), 11);
+        assert_eq!(Interval::new(-3i64, -3).len(), 1);
+        assert_eq!(Interval::from(0u64..=u64::MAX - 1).len(), u64::MAX);
+    }
+
+    #[test]
+    fn test_overlapping_iter() {
+        let tree: IntervalTree<i32> = [(1, 3), (5, 9), (2, 6), (12, 15), (8, 13), (20, 22)]
+            .into_iter()
+            .map(|(l, h)| Interval::new(l, h))
+            .collect();
+        let query = Interval::new(6, 12);
+
+        let lazy: Vec<_> = tree.overlapping(query).copied().collect();
+        assert_eq!(lazy, tree.find_all_overlapping(query));
+        let mut sorted = lazy;
+        sorted.sort();
+        assert_eq!(
+            sorted,
+            vec![Interval::new(2, 6), Interval::new(5, 9), Interval::new(8, 13), Interval::new(12, 15)]
+        );
+        assert_eq!(tree.overlapping(Interval::new(16, 19)).count(), 0);
+        assert!(tree.overlapping(Interval::new(0, 100)).take(1).next().is_some());
+    }
+
+    #[test]
+    fn test_coalesce() {
+        let mut tree = IntervalTree::new();
+        for (l, h) in [(1, 5), (10, 15), (3, 7), (20, 25), (-5, 2), (14, 18)] {
+            tree.insert(l, h);
+        }
+        tree.coalesce();
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.find_at_point(0), vec![Interval::new(-5, 7)]);
+        assert_eq!(tree.find_at_point(16), vec![Interval::new(10, 18)]);
+
+        let mut empty = IntervalTree::<i32>::new();
+        empty.coalesce();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_interval_map() {
+        let map: IntervalMap<i32, &str> =
+            [(Interval::new(0, 9), "a"), (Interval::new(10, 19), "b")]
+                .into_iter()
+                .collect();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.find_at_point(9), vec![(Interval::new(0, 9), &"a")]);
+        assert_eq!(map.find_at_point(10), vec![(Interval::new(10, 19), &"b")]);
+        assert!(map.find_at_point(20).is_empty());
+
+        let mut both = map.find_all_overlapping(Interval::new(5, 12));
+        both.sort();
+        assert_eq!(
+            both,
+            vec![(Interval::new(0, 9), &"a"), (Interval::new(10, 19), &"b")]
+        );
+    }
+
+    #[test]
+    fn test_interval_map_overlapping_entries() {
+        let mut map = IntervalMap::new();
+        map.insert(Interval::new(50, 97), 52);
+        map.insert(Interval::new(98, 99), 50);
+        map.insert(Interval::new(0, 100), 0);
+        let mut hits: Vec<_> = map.find_at_point(98).into_iter().map(|(_, &v)| v).collect();
+        hits.sort();
+        assert_eq!(hits, vec![0, 50]);
+    }
+
+    #[test]
+    fn test_contains_interval() {
+        let outer = Interval::new(2, 8);
+        assert!(outer.contains_interval(&Interval::new(3, 7)));
+        assert!(outer.contains_interval(&Interval::new(2, 8)));
+        assert!(!outer.contains_interval(&Interval::new(6, 9)));
+        assert!(!outer.contains_interval(&Interval::new(1, 3)));
+        assert!(!Interval::new(3, 7).contains_interval(&outer));
+    }
+}
@@ -0,0 +1,119 @@
+//! Slice and iterator helpers for splitting and scanning puzzle input.
+
+/// Splits `0..slice.len()` into half-open `(start, end)` spans, each beginning at an
+/// index whose element matches `is_delimiter` and running up to the next such index.
+///
+/// Any elements before the first delimiter form a leading span of their own. For
+/// example, the operator row of a column-arithmetic puzzle splits into one span per
+/// operator.
+pub fn split_positions<T>(slice: &[T], is_delimiter: impl Fn(&T) -> bool) -> Vec<(usize, usize)> {
+    let mut starts: Vec<usize> = slice
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| is_delimiter(x))
+        .map(|(i, _)| i)
+        .collect();
+    if starts.first() != Some(&0) && !slice.is_empty() {
+        starts.insert(0, 0);
+    }
+    starts.push(slice.len());
+    starts.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+/// Index and value of the largest element in `slice[start..end]`, with the index relative
+/// to the whole slice. Ties go to the earliest element.
+///
+/// Panics if the window is empty or extends past the end of `slice`.
+pub fn window_argmax<T: Copy + Ord>(slice: &[T], start: usize, end: usize) -> (usize, T) {
+    assert!(
+        start < end && end <= slice.len(),
+        "invalid window {start}..{end}"
+    );
+    let mut best = (start, slice[start]);
+    for (idx, &val) in slice.iter().enumerate().take(end).skip(start + 1) {
+        if val > best.1 {
+            best = (idx, val);
+        }
+    }
+    best
+}
+
+/// Groups the lines of `s` into records separated by one or more blank lines, yielding
+/// each record's lines in order.
+///
+/// Lines holding only whitespace count as blank, and leading or trailing blank lines
+/// produce no empty records.
+pub fn chunk_by_blank(s: &str) -> impl Iterator<Item = Vec<&str>> {
+    let is_blank = |line: &&str| line.trim().is_empty();
+    let mut lines = s.lines().peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(is_blank).is_some() {}
+        let mut record = Vec::new();
+        while let Some(line) = lines.next_if(|l| !is_blank(l)) {
+            record.push(line);
+        }
+        (!record.is_empty()).then_some(record)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_op(c: &char) -> bool {
+        *c == '+' || *c == '*'
+    }
+
+    #[test]
+    fn test_split_positions() {
+        let ops: Vec<char> = "*   +   *   +  ".chars().collect();
+        assert_eq!(
+            split_positions(&ops, is_op),
+            vec![(0, 4), (4, 8), (8, 12), (12, 15)]
+        );
+    }
+
+    #[test]
+    fn test_split_positions_leading_and_trailing() {
+        let ops: Vec<char> = "  + *".chars().collect();
+        assert_eq!(split_positions(&ops, is_op), vec![(0, 2), (2, 4), (4, 5)]);
+
+        let ops: Vec<char> = "++".chars().collect();
+        assert_eq!(split_positions(&ops, is_op), vec![(0, 1), (1, 2)]);
+
+        assert!(split_positions(&[] as &[char], is_op).is_empty());
+        assert_eq!(split_positions(&['a', 'b'], is_op), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn test_window_argmax() {
+        let digits = [8, 1, 9, 3, 9, 2];
+        assert_eq!(window_argmax(&digits, 0, 6), (2, 9));
+        assert_eq!(window_argmax(&digits, 3, 6), (4, 9));
+        assert_eq!(window_argmax(&digits, 0, 2), (0, 8));
+        assert_eq!(window_argmax(&digits, 5, 6), (5, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid window 3..3")]
+    fn test_window_argmax_empty_window() {
+        window_argmax(&[8, 1, 9, 3, 9, 2], 3, 3);
+    }
+
+    #[test]
+    fn test_chunk_by_blank() {
+        let input = "\necl:gry pid:860033327\nbyr:1937\n\n\niyr:2013\n\n\
+                     hcl:#ae17e1\n  \neyr:2024\nhgt:179cm\n";
+        let records: Vec<_> = chunk_by_blank(input).collect();
+        assert_eq!(
+            records,
+            vec![
+                vec!["ecl:gry pid:860033327", "byr:1937"],
+                vec!["iyr:2013"],
+                vec!["hcl:#ae17e1"],
+                vec!["eyr:2024", "hgt:179cm"],
+            ]
+        );
+        assert_eq!(chunk_by_blank("\n\n").count(), 0);
+    }
+}
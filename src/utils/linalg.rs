@@ -0,0 +1,87 @@
+//! Exact linear algebra over the rationals.
+
+use num::rational::Rational64;
+use num::{One, Zero};
+
+/// Solves the square system `a · x = b` by Gaussian elimination with rational pivots,
+/// so the answer carries no floating-point error.
+///
+/// Returns `None` if the system is singular or the dimensions do not match.
+pub fn solve_exact(mut a: Vec<Vec<Rational64>>, mut b: Vec<Rational64>) -> Option<Vec<Rational64>> {
+    let n = b.len();
+    if a.len() != n || a.iter().any(|row| row.len() != n) {
+        return None;
+    }
+
+    for col in 0..n {
+        let pivot = (col..n).find(|&row| !a[row][col].is_zero())?;
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let inv = Rational64::one() / a[col][col];
+        for val in &mut a[col][col..] {
+            *val *= inv;
+        }
+        b[col] *= inv;
+
+        let pivot_row = a[col].clone();
+        for row in 0..n {
+            let factor = a[row][col];
+            if row == col || factor.is_zero() {
+                continue;
+            }
+            for (val, &p) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *val -= factor * p;
+            }
+            let delta = factor * b[col];
+            b[row] -= delta;
+        }
+    }
+    Some(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rationals(vals: &[i64]) -> Vec<Rational64> {
+        vals.iter().map(|&v| Rational64::from_integer(v)).collect()
+    }
+
+    #[test]
+    fn test_solve_exact_3x3() {
+        // 2x + 3y + z = 2, x - y + 2z = 43/6, 4x - z = -1
+        let a = vec![
+            rationals(&[2, 3, 1]),
+            rationals(&[1, -1, 2]),
+            rationals(&[4, 0, -1]),
+        ];
+        let b = vec![
+            Rational64::from_integer(2),
+            Rational64::new(43, 6),
+            Rational64::from_integer(-1),
+        ];
+        let x = solve_exact(a, b).unwrap();
+        assert_eq!(
+            x,
+            vec![
+                Rational64::new(1, 2),
+                Rational64::new(-2, 3),
+                Rational64::from_integer(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solve_exact_needs_pivot_swap() {
+        let a = vec![rationals(&[0, 1]), rationals(&[1, 0])];
+        assert_eq!(solve_exact(a, rationals(&[4, 7])), Some(rationals(&[7, 4])));
+    }
+
+    #[test]
+    fn test_solve_exact_singular() {
+        let a = vec![rationals(&[1, 2]), rationals(&[2, 4])];
+        assert_eq!(solve_exact(a, rationals(&[3, 6])), None);
+        assert_eq!(solve_exact(vec![rationals(&[1, 2])], rationals(&[1])), None);
+    }
+}
@@ -7,6 +7,14 @@ pub mod read_lines;
 pub mod simplex;
 pub mod interval_tree;
 pub mod point;
+pub mod numbers;
+pub mod iter;
+pub mod combinatorics;
+pub mod graph;
+pub mod pathfinding;
+pub mod linalg;
+pub mod bitgrid;
+pub mod spatial;
 
 // Optional: You can add "prelude" style re-exports here
 // to make common types easier to access, for example:
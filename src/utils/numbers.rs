@@ -0,0 +1,104 @@
+//! Integer arithmetic helpers: modular counters and small exact linear systems.
+
+/// Simulates a counter on `0..modulus` starting at `start`, applying each
+/// `(clockwise, distance)` move in turn.
+///
+/// Clockwise moves count upwards and anticlockwise moves count downwards. Returns the
+/// final position and the number of times the counter hit `0` along the way, including
+/// landing on it at the end of a move.
+pub fn modular_position(start: i64, moves: &[(bool, i64)], modulus: i64) -> (i64, i64) {
+    let mut pos = start.rem_euclid(modulus);
+    let mut zero_hits = 0;
+
+    for &(clockwise, distance) in moves {
+        if clockwise {
+            zero_hits += (pos + distance) / modulus;
+            pos = (pos + distance) % modulus;
+        } else {
+            let reversed = (modulus - pos) % modulus;
+            zero_hits += (reversed + distance) / modulus;
+            pos = (pos - distance).rem_euclid(modulus);
+        }
+    }
+    (pos, zero_hits)
+}
+
+/// Solves the integer system `[[a0, b0], [a1, b1]] · (x, y) = c` with Cramer's rule.
+///
+/// Returns `None` if the determinant is zero or the unique solution isn't integral.
+pub fn solve_2x2(a: [i64; 2], b: [i64; 2], c: [i64; 2]) -> Option<(i64, i64)> {
+    let det = a[0] * b[1] - a[1] * b[0];
+    if det == 0 {
+        return None;
+    }
+    let x_num = c[0] * b[1] - c[1] * b[0];
+    let y_num = a[0] * c[1] - a[1] * c[0];
+    (x_num % det == 0 && y_num % det == 0).then(|| (x_num / det, y_num / det))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The 2025 day 1 example: L68 L30 R48 L5 R60 L55 L1 L99 R14 L82
+    const DIAL_MOVES: [(bool, i64); 10] = [
+        (false, 68),
+        (false, 30),
+        (true, 48),
+        (false, 5),
+        (true, 60),
+        (false, 55),
+        (false, 1),
+        (false, 99),
+        (true, 14),
+        (false, 82),
+    ];
+
+    #[test]
+    fn test_dial_landings() {
+        let mut pos = 50;
+        let mut landings = 0;
+        for m in DIAL_MOVES {
+            pos = modular_position(pos, &[m], 100).0;
+            landings += i64::from(pos == 0);
+        }
+        assert_eq!(landings, 3);
+    }
+
+    #[test]
+    fn test_dial_zero_hits() {
+        assert_eq!(modular_position(50, &DIAL_MOVES, 100), (32, 6));
+    }
+
+    #[test]
+    fn test_full_wraps() {
+        assert_eq!(modular_position(0, &[(true, 250)], 100), (50, 2));
+        assert_eq!(modular_position(0, &[(false, 250)], 100), (50, 2));
+        assert_eq!(modular_position(10, &[(false, 10)], 100), (0, 1));
+    }
+
+    #[test]
+    fn test_solve_2x2_claw_machines() {
+        // The 2024 day 13 example machines as (button A, button B, prize)
+        let machines = [
+            ([94, 34], [22, 67], [8400, 5400]),
+            ([26, 66], [67, 21], [12748, 12176]),
+            ([17, 86], [84, 37], [7870, 6450]),
+            ([69, 23], [27, 71], [18641, 10279]),
+        ];
+        let solutions: Vec<_> = machines
+            .iter()
+            .map(|&(a, b, c)| solve_2x2(a, b, c))
+            .collect();
+        assert_eq!(solutions, vec![Some((80, 40)), None, Some((38, 86)), None]);
+
+        let tokens: i64 = solutions.iter().flatten().map(|(x, y)| 3 * x + y).sum();
+        assert_eq!(tokens, 480);
+    }
+
+    #[test]
+    fn test_solve_2x2_singular() {
+        assert_eq!(solve_2x2([1, 2], [2, 4], [3, 6]), None);
+        assert_eq!(solve_2x2([1, 0], [0, 1], [-3, 7]), Some((-3, 7)));
+    }
+}
@@ -0,0 +1,347 @@
+//! Shortest-path searches over [`Grid`]s and arbitrary state spaces.
+
+use crate::utils::grid::Grid;
+use crate::utils::point::Point;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Breadth-first search over arbitrary states, returning the fewest steps from `start`
+/// to any state satisfying `is_goal`, or `None` if no goal is reachable.
+pub fn bfs<S, FN, IN>(start: S, is_goal: impl Fn(&S) -> bool, successors: FN) -> Option<usize>
+where
+    S: Hash + Eq + Clone,
+    FN: Fn(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+{
+    let mut visited = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, steps)) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some(steps);
+        }
+        for next in successors(&state) {
+            if visited.insert(next.clone()) {
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm over arbitrary states, returning the minimal cost of reaching
+/// every state reachable from `start`. `neighbours` yields `(next, edge_cost)` pairs.
+pub fn dijkstra<S, FN, IN>(start: S, neighbours: FN) -> HashMap<S, usize>
+where
+    S: Hash + Eq + Clone,
+    FN: Fn(&S) -> IN,
+    IN: IntoIterator<Item = (S, usize)>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut heap = BinaryHeap::from([Frontier {
+        cost: 0,
+        state: start,
+    }]);
+    while let Some(Frontier { cost, state }) = heap.pop() {
+        if cost > distances[&state] {
+            continue;
+        }
+        relax(&mut distances, &mut heap, cost, neighbours(&state));
+    }
+    distances
+}
+
+/// Like [`dijkstra`], but stops as soon as a state satisfying `is_goal` is popped and
+/// returns its cost, or `None` if no goal is reachable.
+pub fn dijkstra_to<S, FN, IN>(
+    start: S,
+    is_goal: impl Fn(&S) -> bool,
+    neighbours: FN,
+) -> Option<usize>
+where
+    S: Hash + Eq + Clone,
+    FN: Fn(&S) -> IN,
+    IN: IntoIterator<Item = (S, usize)>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut heap = BinaryHeap::from([Frontier {
+        cost: 0,
+        state: start,
+    }]);
+    while let Some(Frontier { cost, state }) = heap.pop() {
+        if cost > distances[&state] {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(cost);
+        }
+        relax(&mut distances, &mut heap, cost, neighbours(&state));
+    }
+    None
+}
+
+fn relax<S: Hash + Eq + Clone>(
+    distances: &mut HashMap<S, usize>,
+    heap: &mut BinaryHeap<Frontier<S>>,
+    cost: usize,
+    edges: impl IntoIterator<Item = (S, usize)>,
+) {
+    for (next, edge_cost) in edges {
+        let next_cost = cost + edge_cost;
+        if distances.get(&next).is_none_or(|&best| next_cost < best) {
+            distances.insert(next.clone(), next_cost);
+            heap.push(Frontier {
+                cost: next_cost,
+                state: next,
+            });
+        }
+    }
+}
+
+/// Solves a rotation-cost maze: a walker starts on `start` facing `start_dir` and may
+/// step forward for `step_cost` or turn 90 degrees in place for `turn_cost`.
+///
+/// Returns the minimal cost of reaching `end` in any heading, together with the number
+/// of cells that lie on at least one optimal path, or `None` if `end` is unreachable.
+pub fn maze<T>(
+    grid: &Grid<T>,
+    is_wall: impl Fn(&T) -> bool,
+    start: Point,
+    start_dir: Point,
+    end: Point,
+    step_cost: usize,
+    turn_cost: usize,
+) -> Option<(usize, usize)> {
+    let mut heap = BinaryHeap::new();
+    // (Position, Direction) -> Min Cost
+    let mut distances: HashMap<(Point, Point), usize> = HashMap::new();
+    // (Position, Direction) -> List of optimal previous states
+    let mut predecessors: HashMap<(Point, Point), Vec<(Point, Point)>> = HashMap::new();
+
+    distances.insert((start, start_dir), 0);
+    heap.push(State {
+        cost: 0,
+        pos: start,
+        dir: start_dir,
+    });
+
+    let mut best_total_cost = None;
+
+    while let Some(State { cost, pos, dir }) = heap.pop() {
+        if cost > *distances.get(&(pos, dir)).unwrap_or(&usize::MAX) {
+            continue;
+        }
+        if best_total_cost.is_some_and(|best| cost > best) {
+            break;
+        }
+        if pos == end {
+            best_total_cost = Some(cost);
+        }
+
+        let moves = [
+            (pos + dir, dir, step_cost),
+            (pos, dir.rotate_left_90(), turn_cost),
+            (pos, dir.rotate_right_90(), turn_cost),
+        ];
+
+        for (next_pos, next_dir, move_cost) in moves {
+            if grid.get(next_pos).is_none_or(&is_wall) {
+                continue;
+            }
+
+            let next_cost = cost + move_cost;
+            let state = (next_pos, next_dir);
+            let current_best = *distances.get(&state).unwrap_or(&usize::MAX);
+
+            if next_cost < current_best {
+                distances.insert(state, next_cost);
+                predecessors.insert(state, vec![(pos, dir)]);
+                heap.push(State {
+                    cost: next_cost,
+                    pos: next_pos,
+                    dir: next_dir,
+                });
+            } else if next_cost == current_best {
+                predecessors.entry(state).or_default().push((pos, dir));
+            }
+        }
+    }
+
+    let best_total_cost = best_total_cost?;
+
+    // Backtrack from every heading that reaches `end` at the optimal cost
+    let mut queue: VecDeque<_> = [Point::UP, Point::DOWN, Point::LEFT, Point::RIGHT]
+        .into_iter()
+        .map(|d| (end, d))
+        .filter(|state| distances.get(state) == Some(&best_total_cost))
+        .collect();
+    let mut seen_states = HashSet::new();
+    let mut best_tiles = HashSet::new();
+
+    while let Some(state) = queue.pop_front() {
+        if !seen_states.insert(state) {
+            continue;
+        }
+        best_tiles.insert(state.0);
+        if let Some(preds) = predecessors.get(&state) {
+            queue.extend(preds.iter().copied());
+        }
+    }
+
+    Some((best_total_cost, best_tiles.len()))
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct State {
+    cost: usize,
+    pos: Point,
+    dir: Point,
+}
+
+// BinaryHeap is a max-heap, so we implement Ord such that lower cost has higher priority
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .cmp(&self.cost)
+            .then_with(|| self.pos.cmp(&other.pos))
+            .then_with(|| self.dir.cmp(&other.dir))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A heap entry for the generic searches, ordered by cost alone so states need not be `Ord`
+struct Frontier<S> {
+    cost: usize,
+    state: S,
+}
+
+impl<S> Ord for Frontier<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+impl<S> PartialOrd for Frontier<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> PartialEq for Frontier<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl<S> Eq for Frontier<S> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn solve(input: &str) -> Option<(usize, usize)> {
+        let grid = Grid::<char>::from_str(input).unwrap();
+        let start = grid.find_pos(|&c| c == 'S').unwrap();
+        let end = grid.find_pos(|&c| c == 'E').unwrap();
+        maze(&grid, |&c| c == '#', start, Point::RIGHT, end, 1, 1000)
+    }
+
+    const REINDEER_EXAMPLE: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+    #[test]
+    fn test_maze_reindeer_example() {
+        assert_eq!(solve(REINDEER_EXAMPLE), Some((7036, 45)));
+    }
+
+    #[test]
+    fn test_maze_unreachable() {
+        assert_eq!(solve("#####\n#S#E#\n#####"), None);
+    }
+
+    #[test]
+    fn test_bfs_light_toggling() {
+        // The 2025 day 10 example machines as (target lights, buttons), with bit i for
+        // light i; each press XORs a button into the state
+        let machines: [(usize, &[usize]); 3] = [
+            (0b0110, &[0b1000, 0b1010, 0b0100, 0b1100, 0b0101, 0b0011]),
+            (0b01000, &[0b11101, 0b01100, 0b10001, 0b00111, 0b11110]),
+            (0b101110, &[0b011111, 0b011001, 0b110111, 0b000110]),
+        ];
+        let presses: Vec<_> = machines
+            .iter()
+            .map(|&(target, buttons)| {
+                bfs(
+                    0usize,
+                    |&s| s == target,
+                    |&s| buttons.iter().map(move |b| s ^ b),
+                )
+            })
+            .collect();
+        assert_eq!(presses, vec![Some(2), Some(3), Some(2)]);
+        assert_eq!(presses.iter().flatten().sum::<usize>(), 7);
+    }
+
+    #[test]
+    fn test_bfs_unreachable() {
+        // Stepping by 2 from an odd start never lands on 0 mod 8
+        assert_eq!(bfs(1u32, |&s| s == 0, |&s| [(s + 2) % 8]), None);
+        assert_eq!(bfs(1u32, |&s| s == 7, |&s| [(s + 2) % 8]), Some(3));
+    }
+
+    #[test]
+    fn test_dijkstra_to_matches_full_map() {
+        let grid = Grid::<char>::from_str(REINDEER_EXAMPLE).unwrap();
+        let start = grid.find_pos(|&c| c == 'S').unwrap();
+        let end = grid.find_pos(|&c| c == 'E').unwrap();
+        let neighbours = |&(pos, dir): &(Point, Point)| {
+            let forward = (grid.get(pos + dir) != Some(&'#')).then_some(((pos + dir, dir), 1));
+            [
+                ((pos, dir.rotate_left_90()), 1000),
+                ((pos, dir.rotate_right_90()), 1000),
+            ]
+            .into_iter()
+            .chain(forward)
+        };
+
+        let distances = dijkstra((start, Point::RIGHT), neighbours);
+        let full = distances
+            .iter()
+            .filter(|((pos, _), _)| *pos == end)
+            .map(|(_, &cost)| cost)
+            .min();
+        let early = dijkstra_to((start, Point::RIGHT), |&(pos, _)| pos == end, neighbours);
+        assert_eq!(full, Some(7036));
+        assert_eq!(early, full);
+    }
+
+    #[test]
+    fn test_dijkstra_to_unreachable() {
+        assert_eq!(
+            dijkstra_to(0u32, |&s| s == 5, |&s| [((s + 2) % 4, 1)]),
+            None
+        );
+        assert_eq!(dijkstra(0u32, |&s| [((s + 2) % 4, 1)]).len(), 2);
+    }
+}
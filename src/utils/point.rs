@@ -1,9 +1,11 @@
 //! A 2D coordinate utility for grid-based puzzles.
 //!
 //! Optimized for a y-down coordinate system (standard in grids).
-//! Provides vector arithmetic and rotation logic.
+//! Provides vector arithmetic and rotation logic, plus a minimal [`Point3`]
+//! for voxel puzzles.
 
-use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+use std::collections::HashSet;
+use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
 
 /// A point or vector in 2D space.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -31,13 +33,40 @@ impl Point {
         self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
     }
 
+    /// Returns the Manhattan distance between two points as a `u64`.
+    ///
+    /// Unlike [`Point::manhattan_distance`], this cannot overflow for any pair of `i32` coordinates.
+    pub fn manhattan_distance_u64(&self, other: &Self) -> u64 {
+        u64::from(self.x.abs_diff(other.x)) + u64::from(self.y.abs_diff(other.y))
+    }
+
+    /// Returns the signed `(dx, dy)` step from `self` to `other`.
+    ///
+    /// Computed in `i64` so that far-apart `i32` coordinates don't overflow.
+    pub fn delta(&self, other: &Self) -> (i64, i64) {
+        (
+            i64::from(other.x) - i64::from(self.x),
+            i64::from(other.y) - i64::from(self.y),
+        )
+    }
+
+    /// Returns the integer midpoint of `self` and `other`.
+    ///
+    /// Rounds each coordinate toward zero when the true centre falls between cells, e.g.
+    /// `(0, 0)` and `(3, -3)` give `(1, -1)`. Summed in `i64`, so it cannot overflow.
+    pub fn midpoint(&self, other: Point) -> Point {
+        let mid = |a: i32, b: i32| ((i64::from(a) + i64::from(b)) / 2) as i32;
+        Point::new(mid(self.x, other.x), mid(self.y, other.y))
+    }
+
     /// Returns the squared Euclidean distance between two points.
     ///
     /// Useful for comparing distances without the overhead of a square root.
+    /// Computed in `u64` so that large coordinates don't overflow.
     /// Formula: $d^2 = (x_1 - x_2)^2 + (y_1 - y_2)^2$
-    pub fn euclidean_squared(&self, other: &Self) -> u32 {
-        let dx = self.x.abs_diff(other.x);
-        let dy = self.y.abs_diff(other.y);
+    pub fn euclidean_squared(&self, other: &Self) -> u64 {
+        let dx = u64::from(self.x.abs_diff(other.x));
+        let dy = u64::from(self.y.abs_diff(other.y));
         dx * dx + dy * dy
     }
 
@@ -64,12 +93,37 @@ impl Point {
         Point::new(self.y, -self.x)
     }
 
+    /// Applies `turns` clockwise quarter turns (y-down); only `turns % 4` matters.
+    pub fn rotate_cw(&self, turns: u8) -> Self {
+        match turns % 4 {
+            0 => *self,
+            1 => self.rotate_right_90(),
+            2 => self.reverse(),
+            _ => self.rotate_left_90(),
+        }
+    }
+
+    /// Applies `turns` counter-clockwise quarter turns (y-down); only `turns % 4` matters.
+    pub fn rotate_ccw(&self, turns: u8) -> Self {
+        self.rotate_cw((4 - turns % 4) % 4)
+    }
+
     /// Rotates the vector 180 degrees.
     #[inline]
     pub fn reverse(&self) -> Self {
         Point::new(-self.x, -self.y)
     }
 
+    /// Returns the primitive step from `self` towards `other`: the difference divided by
+    /// the gcd of its components, keeping the signs. Equal points give the zero vector.
+    pub fn reduced_direction(&self, other: Point) -> Self {
+        let delta = other - *self;
+        match num::integer::gcd(delta.x, delta.y) {
+            0 => delta,
+            g => delta / g,
+        }
+    }
+
     /// Returns an iterator of all integer points on a straight line between
     /// self and other (inclusive). Handles horizontal, vertical, and 45-degree lines.
     pub fn points_between(&self, other: Point) -> Vec<Point> {
@@ -91,6 +145,43 @@ impl Point {
         points
     }
 
+    /// Returns the four cardinal neighbours, without any bounds checking.
+    ///
+    /// Useful for sparse grids (e.g. a `HashSet<Point>`) with no backing [`Grid`](crate::utils::grid::Grid).
+    pub fn neighbors4(self) -> impl Iterator<Item = Point> {
+        [Point::UP, Point::DOWN, Point::LEFT, Point::RIGHT]
+            .into_iter()
+            .map(move |dir| self + dir)
+    }
+
+    /// Returns all eight surrounding neighbours (including diagonals), without any bounds checking.
+    pub fn neighbors8(self) -> impl Iterator<Item = Point> {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| dx != 0 || dy != 0)
+            .map(move |(dx, dy)| self + Point::new(dx, dy))
+    }
+
+    /// Every point within Manhattan distance `radius` of `self` (inclusive), row by row.
+    ///
+    /// Yields `2r² + 2r + 1` points in total.
+    pub fn manhattan_disk(&self, radius: u32) -> impl Iterator<Item = Point> {
+        let center = *self;
+        let r = radius as i32;
+        (-r..=r).flat_map(move |dy| {
+            let span = r - dy.abs();
+            (-span..=span).map(move |dx| center + Point::new(dx, dy))
+        })
+    }
+
+    /// Multiplies two points component-wise.
+    ///
+    /// Formula: (x₁, y₁) ⊙ (x₂, y₂) = (x₁x₂, y₁y₂)
+    #[inline]
+    pub fn component_mul(self, other: Point) -> Self {
+        Point::new(self.x * other.x, self.y * other.y)
+    }
+
     pub fn wrap(&self, width: i32, height: i32) -> Self {
         Point::new(
             ((self.x % width) + width) % width,
@@ -99,6 +190,16 @@ impl Point {
     }
 }
 
+/// Counts the neighbours of `p` present in the sparse occupancy `set`, looking at all
+/// eight surrounding cells if `diagonal` is set and only the four cardinal ones otherwise.
+pub fn occupied_neighbors(p: Point, set: &HashSet<Point>, diagonal: bool) -> usize {
+    if diagonal {
+        p.neighbors8().filter(|n| set.contains(n)).count()
+    } else {
+        p.neighbors4().filter(|n| set.contains(n)).count()
+    }
+}
+
 // --- Operator Overloads ---
 
 impl Add for Point {
@@ -140,6 +241,74 @@ impl Mul<i32> for Point {
     }
 }
 
+/// Scalar division of both components.
+///
+/// Uses integer division, so each component is truncated towards zero
+/// (e.g. `Point::new(7, -7) / 2 == Point::new(3, -3)`).
+impl Div<i32> for Point {
+    type Output = Self;
+    fn div(self, scalar: i32) -> Self {
+        Point::new(self.x / scalar, self.y / scalar)
+    }
+}
+
+/// A point or vector in 3D space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point3 {
+    /// Creates a new point.
+    #[inline]
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the 6 points sharing a face with this one (one axis changed by 1).
+    pub fn face_neighbors(&self) -> [Point3; 6] {
+        let Point3 { x, y, z } = *self;
+        [
+            Point3::new(x - 1, y, z),
+            Point3::new(x + 1, y, z),
+            Point3::new(x, y - 1, z),
+            Point3::new(x, y + 1, z),
+            Point3::new(x, y, z - 1),
+            Point3::new(x, y, z + 1),
+        ]
+    }
+
+    /// Returns all 26 points of the surrounding 3x3x3 cube, excluding this one.
+    pub fn all_neighbors(&self) -> [Point3; 26] {
+        let mut neighbors = [*self; 26];
+        let deltas = (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+            .filter(|&d| d != (0, 0, 0));
+        for (n, (dx, dy, dz)) in neighbors.iter_mut().zip(deltas) {
+            *n = *self + Point3::new(dx, dy, dz);
+        }
+        neighbors
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
 // --- Unit Tests ---
 
 #[cfg(test)]
@@ -303,4 +472,175 @@ mod tests {
         let p3 = Point::new(100, 100);
         assert_eq!(p3.wrap(100, 100), Point::new(0, 0));
     }
+
+    #[test]
+    fn test_scalar_division() {
+        // Exact division
+        assert_eq!(Point::new(10, -20) / 2, Point::new(5, -10));
+
+        // Truncating division rounds towards zero on both signs
+        assert_eq!(Point::new(7, -7) / 2, Point::new(3, -3));
+        assert_eq!(Point::new(101, 103) / 2, Point::new(50, 51));
+    }
+
+    #[test]
+    fn test_component_mul() {
+        let p1 = Point::new(3, -4);
+        let p2 = Point::new(5, 2);
+        assert_eq!(p1.component_mul(p2), Point::new(15, -8));
+        assert_eq!(p1.component_mul(Point::new(1, 1)), p1);
+    }
+
+    #[test]
+    fn test_subtraction_past_origin() {
+        // Points are signed, so reflecting past the origin yields negative
+        // coordinates rather than underflowing; callers bounds-check with
+        // `Grid::in_bounds` instead.
+        let a = Point::new(4, 3);
+        let b = Point::new(5, 5);
+        let delta = a - b;
+        assert_eq!(delta, Point::new(-1, -2));
+        assert_eq!(a + delta, Point::new(3, 1));
+        assert_eq!(Point::new(0, 1) + delta, Point::new(-1, -1));
+    }
+
+    #[test]
+    fn test_unbounded_neighbors() {
+        let origin = Point::new(0, 0);
+        let n4: Vec<_> = origin.neighbors4().collect();
+        assert_eq!(n4.len(), 4);
+        assert!(n4.contains(&Point::new(0, -1)));
+        assert!(n4.contains(&Point::new(-1, 0)));
+
+        let n8: Vec<_> = origin.neighbors8().collect();
+        assert_eq!(n8.len(), 8);
+        assert!(n8.contains(&Point::new(-1, -1)));
+        assert!(n8.contains(&Point::new(1, 1)));
+        assert!(!n8.contains(&origin));
+    }
+
+    #[test]
+    fn test_large_coordinate_distances() {
+        // dx = dy = 200_000, so dx² alone overflows a u32
+        let p1 = Point::new(-100_000, -100_000);
+        let p2 = Point::new(100_000, 100_000);
+        assert_eq!(p1.euclidean_squared(&p2), 80_000_000_000);
+
+        // The sum of both axes overflows a u32 at the i32 extremes
+        let p3 = Point::new(i32::MIN, i32::MIN);
+        let p4 = Point::new(i32::MAX, i32::MAX);
+        assert_eq!(p3.manhattan_distance_u64(&p4), 2 * u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn test_reduced_direction() {
+        let origin = Point::new(1, 1);
+        assert_eq!(origin.reduced_direction(Point::new(1, -5)), Point::UP);
+        assert_eq!(origin.reduced_direction(Point::new(8, 1)), Point::RIGHT);
+        assert_eq!(origin.reduced_direction(Point::new(-2, 4)), Point::new(-1, 1));
+        assert_eq!(origin.reduced_direction(Point::new(3, 5)), Point::new(1, 2));
+        assert_eq!(origin.reduced_direction(Point::new(-1, -3)), Point::new(-1, -2));
+        assert_eq!(origin.reduced_direction(origin), Point::new(0, 0));
+    }
+
+    #[test]
+    fn test_manhattan_disk() {
+        let center = Point::new(3, -2);
+        let mut disk: Vec<_> = center.manhattan_disk(1).collect();
+        disk.sort();
+        let mut expected: Vec<_> = std::iter::once(center).chain(center.neighbors4()).collect();
+        expected.sort();
+        assert_eq!(disk, expected);
+
+        assert_eq!(center.manhattan_disk(0).collect::<Vec<_>>(), vec![center]);
+        for r in 0..6u32 {
+            let points: Vec<_> = center.manhattan_disk(r).collect();
+            assert_eq!(points.len() as u32, 2 * r * r + 2 * r + 1);
+            assert!(points.iter().all(|p| p.manhattan_distance(&center) <= r));
+        }
+    }
+
+    #[test]
+    fn test_point3_neighbors() {
+        let p = Point3::new(1, -2, 3);
+
+        let faces = p.face_neighbors();
+        assert_eq!(faces.len(), 6);
+        for n in faces {
+            let d = n - p;
+            // Exactly one axis moves for a face neighbour, so no diagonals
+            assert_eq!(d.x.abs() + d.y.abs() + d.z.abs(), 1);
+        }
+
+        let all = p.all_neighbors();
+        let unique: std::collections::HashSet<_> = all.iter().collect();
+        assert_eq!(unique.len(), 26);
+        assert!(!unique.contains(&p));
+        assert!(faces.iter().all(|f| unique.contains(f)));
+        assert!(unique.contains(&Point3::new(2, -1, 4)));
+    }
+
+    #[test]
+    fn test_delta() {
+        let a = Point::new(5, 7);
+        let b = Point::new(2, 3);
+        assert_eq!(a.delta(&b), (-3, -4));
+        assert_eq!(b.delta(&a), (3, 4));
+        assert_eq!(
+            Point::new(i32::MIN, 0).delta(&Point::new(i32::MAX, 0)),
+            (u32::MAX as i64, 0)
+        );
+    }
+
+    #[test]
+    fn test_rotate_by_turns() {
+        let p = Point::new(3, -1);
+        assert_eq!(
+            p.rotate_cw(3),
+            p.rotate_right_90().rotate_right_90().rotate_right_90()
+        );
+        assert_eq!(p.rotate_cw(4), p);
+        assert_eq!(p.rotate_cw(6), p.reverse());
+        assert_eq!(p.rotate_ccw(1), p.rotate_left_90());
+        assert_eq!(p.rotate_ccw(3), p.rotate_cw(1));
+        assert_eq!(p.rotate_ccw(255).rotate_cw(255), p);
+    }
+
+    #[test]
+    fn test_occupied_neighbors() {
+        // #.#
+        // .X#
+        // #..
+        let set = HashSet::from([
+            Point::new(0, 0),
+            Point::new(2, 0),
+            Point::new(2, 1),
+            Point::new(0, 2),
+            Point::new(1, 1),
+        ]);
+        let centre = Point::new(1, 1);
+        assert_eq!(occupied_neighbors(centre, &set, false), 1);
+        assert_eq!(occupied_neighbors(centre, &set, true), 4);
+        assert_eq!(occupied_neighbors(Point::new(5, 5), &set, true), 0);
+    }
+
+    #[test]
+    fn test_midpoint() {
+        assert_eq!(
+            Point::new(2, 4).midpoint(Point::new(6, 10)),
+            Point::new(4, 7)
+        );
+        assert_eq!(
+            Point::new(0, 0).midpoint(Point::new(3, -3)),
+            Point::new(1, -1)
+        );
+        assert_eq!(
+            Point::new(0, 0).midpoint(Point::new(100, 102)),
+            Point::new(50, 51)
+        );
+        assert_eq!(
+            Point::new(i32::MAX, 0).midpoint(Point::new(i32::MAX, 0)),
+            Point::new(i32::MAX, 0)
+        );
+    }
 }
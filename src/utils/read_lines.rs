@@ -1,3 +1,8 @@
+use crate::utils::grid::Grid;
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+
 pub fn as_lines(input: &str) -> Vec<&str> {
     input.lines().collect()
 }
@@ -10,4 +15,143 @@ where
     input.lines()
         .map(|line| line.parse::<T>().unwrap())
         .collect()
-}
\ No newline at end of file
+}
+
+/// Splits each line of `input` into its characters.
+pub fn char_rows(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+/// Parses each line of `input` as a row of decimal digits.
+pub fn digit_rows(input: &str) -> Result<Vec<Vec<u32>>> {
+    input
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| c.to_digit(10).with_context(|| format!("Invalid digit {c:?}")))
+                .collect()
+        })
+        .collect()
+}
+
+/// Splits `input` into blank-line-separated sections, dropping empty ones.
+pub fn blocks(input: &str) -> Vec<&str> {
+    input
+        .split("\n\n")
+        .map(|block| block.trim_matches('\n'))
+        .filter(|block| !block.is_empty())
+        .collect()
+}
+
+/// Splits `input` at the first blank line into the first section and the rest.
+///
+/// Later blank lines are left inside the second section.
+pub fn two_blocks(input: &str) -> Result<(&str, &str)> {
+    match input.split_once("\n\n") {
+        Some((first, second)) => Ok((first, second)),
+        None => bail!("Expected two blank-line-separated sections"),
+    }
+}
+
+/// Parses blank-line-separated blocks whose first line is a label (an optional
+/// trailing `:` is stripped) and whose remaining lines form a character grid.
+pub fn labeled_grids(input: &str) -> Result<Vec<(String, Grid<char>)>> {
+    blocks(input)
+        .into_iter()
+        .map(|block| {
+            Grid::from_labeled_block(block)
+                .with_context(|| format!("Block {:?} is not a labelled grid", block))
+        })
+        .collect()
+}
+
+/// Default directory holding puzzle inputs, relative to the crate root.
+pub const DEFAULT_INPUT_DIR: &str = "input";
+
+/// Path to the puzzle input for `year`/`day` under [`DEFAULT_INPUT_DIR`].
+pub fn input_path(year: u32, day: u32) -> PathBuf {
+    input_path_in(Path::new(DEFAULT_INPUT_DIR), year, day)
+}
+
+/// Path to the puzzle input for `year`/`day` under `dir`.
+pub fn input_path_in(dir: &Path, year: u32, day: u32) -> PathBuf {
+    dir.join(format!("year{}", year))
+        .join(format!("day{:02}.txt", day))
+}
+
+/// Reads the puzzle input for `year`/`day` from [`input_path`].
+pub fn read_input(year: u32, day: u32) -> Result<String> {
+    read_input_from(Path::new(DEFAULT_INPUT_DIR), year, day)
+}
+
+/// Reads the puzzle input for `year`/`day` from [`input_path_in`] `dir`.
+pub fn read_input_from(dir: &Path, year: u32, day: u32) -> Result<String> {
+    let path = input_path_in(dir, year, day);
+    fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(input_path(2025, 3), Path::new("input/year2025/day03.txt"));
+        assert_eq!(input_path(2024, 16), Path::new("input/year2024/day16.txt"));
+    }
+
+    #[test]
+    fn test_input_path_in() {
+        let dir = Path::new("inputs/alice");
+        assert_eq!(
+            input_path_in(dir, 2025, 3),
+            Path::new("inputs/alice/year2025/day03.txt")
+        );
+        assert_ne!(input_path_in(dir, 2025, 3), input_path(2025, 3));
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(blocks("a\nb\n\nc\n\n\nd\n"), vec!["a\nb", "c", "d"]);
+        assert!(blocks("").is_empty());
+    }
+
+    #[test]
+    fn test_two_blocks() {
+        assert_eq!(two_blocks("1|2\n3|4\n\n1,2").unwrap(), ("1|2\n3|4", "1,2"));
+        assert!(two_blocks("1|2\n3|4").is_err());
+    }
+
+    #[test]
+    fn test_labeled_grids() {
+        let shapes = "\
+0:\n###\n##.\n##.\n\n1:\n###\n##.\n.##\n\n2:\n.##\n###\n##.\n\n\
+3:\n##.\n###\n##.\n\n4:\n###\n#..\n###\n\n5:\n###\n.#.\n###";
+        let grids = labeled_grids(shapes).unwrap();
+
+        assert_eq!(grids.len(), 6);
+        for (i, (label, grid)) in grids.iter().enumerate() {
+            assert_eq!(label, &i.to_string());
+            assert_eq!((grid.width, grid.height), (3, 3));
+        }
+        assert_eq!(grids[4].1.to_string(), "###\n#..\n###\n");
+
+        assert!(labeled_grids("0:").is_err());
+    }
+
+    #[test]
+    fn test_char_rows() {
+        assert_eq!(char_rows("S.\n^."), vec![vec!['S', '.'], vec!['^', '.']]);
+        assert!(char_rows("").is_empty());
+    }
+
+    #[test]
+    fn test_digit_rows() {
+        // The 2025 day 3 example's first two banks
+        let rows = digit_rows("987654321111111\n811111111111119").unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(rows[1][14], 9);
+        assert!(digit_rows("12\n3x").is_err());
+    }
+}
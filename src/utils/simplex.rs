@@ -10,7 +10,7 @@ use itertools::Itertools;
 use nalgebra::{ComplexField, Const, DMatrix, DVector, Dyn, OMatrix, U1, Vector1, stack};
 use num::rational::Rational64;
 use num::{Signed, Zero};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::fmt;
 use std::fmt::Formatter;
 use thiserror::Error;
@@ -22,6 +22,18 @@ pub(crate) enum LPOps {
     Lte,
 }
 
+#[derive(Error, Debug, PartialEq, Eq)]
+pub(crate) enum LPError {
+    #[error("constraint {index} has {found} coefficients, expected {expected}")]
+    ConstraintLength {
+        index: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("{constraints} constraint rows but {rhs} right-hand sides")]
+    RhsLength { constraints: usize, rhs: usize },
+}
+
 fn to_rational(x: i64) -> Rational64 {
     Rational64::from_integer(x)
 }
@@ -39,11 +51,45 @@ impl LPBuilder {
         Self::default()
     }
 
+    /// Sets up the "fewest total selections" problem: minimise the sum of all
+    /// variables subject to `coeff[i] · x == rhs[i]` for every row `i`.
+    ///
+    /// Fails if `coeff` and `rhs` have different lengths.
+    pub(crate) fn minimize_presses(coeff: &[Vec<i64>], rhs: &[i64]) -> Result<Self, LPError> {
+        if coeff.len() != rhs.len() {
+            return Err(LPError::RhsLength {
+                constraints: coeff.len(),
+                rhs: rhs.len(),
+            });
+        }
+        let mut builder = Self::new();
+        for (row, &ans) in coeff.iter().zip(rhs) {
+            builder.add_constraint(row.clone(), LPOps::Eq, ans);
+        }
+        let n = coeff.first().map_or(0, Vec::len);
+        builder.add_objective(vec![1; n]);
+        Ok(builder)
+    }
+
     pub(crate) fn add_objective(&mut self, objective: Vec<i64>) -> &mut Self {
         self.objective = objective;
         self
     }
 
+    /// Removes the objective and every constraint so the builder can be reused.
+    pub(crate) fn clear(&mut self) -> &mut Self {
+        self.objective.clear();
+        self.constraints.clear();
+        self.ans.clear();
+        self.ops.clear();
+        self
+    }
+
+    /// Adds the constraint `constraint · x <op> ans`, normalising it so that `ans` is
+    /// non-negative.
+    ///
+    /// A constraint identical (after normalisation) to one already added is skipped, so
+    /// repeated rows don't grow the tableau.
     pub(crate) fn add_constraint(
         &mut self,
         mut constraint: Vec<i64>,
@@ -79,9 +125,29 @@ impl LPBuilder {
         self
     }
 
-    pub(crate) fn build(&self) -> LinearProgrammingProblem {
+    /// Number of decision variables, as given by the objective function.
+    pub(crate) fn num_variables(&self) -> usize {
+        self.objective.len()
+    }
+
+    /// Builds the simplex tableau, checking that every constraint has one
+    /// coefficient per decision variable.
+    pub(crate) fn build(&self) -> Result<LinearProgrammingProblem, LPError> {
         let m = self.constraints.len();
-        let n_x = self.constraints.first().map(|v| v.len()).unwrap_or(0);
+        let n_x = self.num_variables();
+
+        if let Some((index, c)) = self
+            .constraints
+            .iter()
+            .enumerate()
+            .find(|(_, c)| c.len() != n_x)
+        {
+            return Err(LPError::ConstraintLength {
+                index,
+                expected: n_x,
+                found: c.len(),
+            });
+        }
 
         let n_slack = self
             .ops
@@ -179,7 +245,7 @@ impl LPBuilder {
             }
         }
 
-        lp
+        Ok(lp)
     }
 }
 
@@ -375,6 +441,15 @@ impl LinearProgrammingProblem {
         }
     }
 
+    /// Reads the objective value from the phase-2 objective row of the tableau.
+    ///
+    /// Only meaningful after a successful [`maximize`](Self::maximize), where it equals the
+    /// returned value. After [`minimize`](Self::minimize) it is the negated minimum, since
+    /// minimisation maximises the negated objective.
+    pub fn current_objective(&self) -> Rational64 {
+        self.rhs(self.n_constraints)
+    }
+
     pub fn solution_x(&self) -> Vec<Rational64> {
         let mut x = vec![Rational64::ZERO; self.slack_var_start];
 
@@ -389,38 +464,95 @@ impl LinearProgrammingProblem {
     }
 }
 
-pub(crate) fn branch_and_bound(root: LPBuilder, n: usize) -> Option<i64> {
-    let mut best: Option<i64> = None;
-    let mut stack = vec![root];
-    while let Some(b) = stack.pop() {
-        let mut lp = b.clone().build();
-        let Some(obj) = lp.minimize() else {
-            continue; // infeasible/unbounded node
+/// A branch-and-bound node, ordered so that the node with the lowest LP
+/// relaxation bound has the highest priority in a `BinaryHeap`.
+struct BranchNode {
+    bound: Rational64,
+    builder: LPBuilder,
+    x: Vec<Rational64>,
+}
+
+impl BranchNode {
+    /// Solves the LP relaxation for `builder`, returning `None` if it is infeasible or unbounded.
+    fn solve(builder: LPBuilder) -> Result<Option<Self>, LPError> {
+        let mut lp = builder.build()?;
+        let Some(bound) = lp.minimize() else {
+            return Ok(None);
         };
-        let node_lb = obj.ceil();
-        if let Some(best_val) = best
-            && node_lb >= best_val.into()
+        let x = lp.solution_x();
+        Ok(Some(Self { bound, builder, x }))
+    }
+}
+
+impl PartialEq for BranchNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.bound == other.bound
+    }
+}
+
+impl Eq for BranchNode {}
+
+impl PartialOrd for BranchNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BranchNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.bound.cmp(&self.bound)
+    }
+}
+
+/// Solves the integer linear program `root` (a minimisation over `n` variables)
+/// using best-first branch and bound, returning the optimal objective value and
+/// the integer assignment achieving it.
+///
+/// Nodes are expanded in order of their LP relaxation bound. If `max_nodes` is
+/// given and that many nodes are expanded before optimality is proven, `None`
+/// is returned. A malformed `root` is reported as an [`LPError`] rather than
+/// being mistaken for an infeasible program.
+pub(crate) fn branch_and_bound(
+    root: LPBuilder,
+    n: usize,
+    max_nodes: Option<usize>,
+) -> Result<Option<(i64, Vec<i64>)>, LPError> {
+    let mut best: Option<(i64, Vec<i64>)> = None;
+    let mut heap: BinaryHeap<BranchNode> = BranchNode::solve(root)?.into_iter().collect();
+    let mut expanded = 0;
+
+    while let Some(node) = heap.pop() {
+        // Every remaining node has a bound at least this large, so none can improve on the incumbent.
+        if let Some((best_val, _)) = best
+            && node.bound.ceil() >= best_val.into()
         {
-            continue;
+            break;
         }
-        let x = lp.solution_x();
-        if let Some((k, xk)) = x.iter().enumerate().find(|(_, v)| !v.is_integer()) {
+        if max_nodes.is_some_and(|max| expanded >= max) {
+            return Ok(None);
+        }
+        expanded += 1;
+
+        if let Some((k, xk)) = node.x.iter().enumerate().find(|(_, v)| !v.is_integer()) {
             let lo = xk.floor().to_integer();
             let hi = xk.ceil().to_integer();
-            let mut b_le = b.clone();
             let mut v = vec![0; n];
             v[k] = 1;
+            let mut b_le = node.builder.clone();
             b_le.add_constraint(v.clone(), LPOps::Lte, lo);
-            let mut b_ge = b;
+            let mut b_ge = node.builder;
             b_ge.add_constraint(v, LPOps::Gte, hi);
-            stack.push(b_le);
-            stack.push(b_ge);
+            heap.extend(BranchNode::solve(b_le)?);
+            heap.extend(BranchNode::solve(b_ge)?);
         } else {
-            let obj_i = obj.to_integer();
-            best = Some(best.map_or(obj_i, |cur| cur.min(obj_i)));
+            let obj_i = node.bound.to_integer();
+            if best.as_ref().is_none_or(|&(cur, _)| obj_i < cur) {
+                let x = node.x.iter().map(|v| v.to_integer()).collect();
+                best = Some((obj_i, x));
+            }
         }
     }
-    best
+    Ok(best)
 }
 
 #[cfg(test)]
@@ -437,7 +569,7 @@ mod tests {
     }
 
     fn lp_solver() -> LinearProgrammingProblem {
-        lp_builder().build()
+        lp_builder().build().unwrap()
     }
 
     #[test]
@@ -456,4 +588,140 @@ mod tests {
         let mut solver = lp_solver();
         assert_eq!(solver.maximize(), Some(Rational64::from_integer(20)));
     }
+
+    #[test]
+    fn test_minimize_presses() {
+        // The three machines from 2025 day 10: each button adds one to the counters it lists.
+        let machines: [(&[&[usize]], &[i64]); 3] = [
+            (&[&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]], &[3, 5, 4, 7]),
+            (
+                &[&[0, 2, 3, 4], &[2, 3], &[0, 4], &[0, 1, 2], &[1, 2, 3, 4]],
+                &[7, 5, 12, 7, 2],
+            ),
+            (
+                &[&[0, 1, 2, 3, 4], &[0, 3, 4], &[0, 1, 2, 4, 5], &[1, 2]],
+                &[10, 11, 11, 5, 10, 5],
+            ),
+        ];
+
+        let mut total = 0;
+        for (buttons, joltage) in machines {
+            let mut coeff = vec![vec![0; buttons.len()]; joltage.len()];
+            for (b, counters) in buttons.iter().enumerate() {
+                for &c in counters.iter() {
+                    coeff[c][b] = 1;
+                }
+            }
+            let builder = LPBuilder::minimize_presses(&coeff, joltage).unwrap();
+            assert_eq!(builder.num_variables(), buttons.len());
+            total += branch_and_bound(builder, buttons.len(), None)
+                .unwrap()
+                .unwrap()
+                .0;
+        }
+        assert_eq!(total, 33);
+    }
+
+    #[test]
+    fn test_current_objective() {
+        let mut solver = lp_solver();
+        let optimum = solver.maximize().unwrap();
+        assert_eq!(solver.current_objective(), optimum);
+
+        let mut lp = fractional_ilp().build().unwrap();
+        let minimum = lp.minimize().unwrap();
+        assert_eq!(lp.current_objective(), -minimum);
+    }
+
+    #[test]
+    fn test_num_variables() {
+        assert_eq!(lp_builder().num_variables(), 3);
+        assert_eq!(LPBuilder::new().num_variables(), 0);
+    }
+
+    #[test]
+    fn test_build_rejects_mismatched_constraints() {
+        let mut builder = lp_builder();
+        builder.add_constraint(vec![1, 1], LPOps::Lte, 4);
+        assert_eq!(
+            builder.build().err(),
+            Some(LPError::ConstraintLength {
+                index: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    fn fractional_ilp() -> LPBuilder {
+        // min x + y  s.t.  2x + 2y >= 3, whose LP relaxation has optimum 1.5
+        let mut builder = LPBuilder::new();
+        builder.add_objective(vec![1, 1]);
+        builder.add_constraint(vec![2, 2], LPOps::Gte, 3);
+        builder
+    }
+
+    #[test]
+    fn test_branch_and_bound_optimal() {
+        let (cost, x) = branch_and_bound(fractional_ilp(), 2, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(cost, 2);
+        assert_eq!(x.iter().sum::<i64>(), 2);
+        assert_eq!(branch_and_bound(fractional_ilp(), 2, Some(100)), Ok(Some((cost, x))));
+    }
+
+    #[test]
+    fn test_branch_and_bound_solution_vector() {
+        // Claw machine: A = (94, 34), B = (22, 67), prize at (8400, 5400); A costs 3, B costs 1
+        let mut builder = LPBuilder::new();
+        builder.add_constraint(vec![94, 22], LPOps::Eq, 8400);
+        builder.add_constraint(vec![34, 67], LPOps::Eq, 5400);
+        builder.add_objective(vec![3, 1]);
+
+        let (cost, presses) = branch_and_bound(builder, 2, None).unwrap().unwrap();
+        assert_eq!(cost, 280);
+        assert_eq!(presses, vec![80, 40]);
+        assert_eq!(94 * presses[0] + 22 * presses[1], 8400);
+        assert_eq!(34 * presses[0] + 67 * presses[1], 5400);
+    }
+
+    #[test]
+    fn test_branch_and_bound_node_limit() {
+        // The root relaxation is fractional, so a single node can't prove optimality
+        assert_eq!(branch_and_bound(fractional_ilp(), 2, Some(1)), Ok(None));
+    }
+
+    #[test]
+    fn test_branch_and_bound_rejects_malformed_root() {
+        let mut builder = fractional_ilp();
+        builder.add_constraint(vec![1], LPOps::Lte, 4);
+        assert_eq!(
+            branch_and_bound(builder, 2, None),
+            Err(LPError::ConstraintLength {
+                index: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut builder = LPBuilder::minimize_presses(&[vec![1, 1], vec![0, 1]], &[3, 2]).unwrap();
+        builder.add_constraint(vec![1, 0], LPOps::Gte, 1);
+        builder.clear();
+        assert_eq!(builder, LPBuilder::new());
+    }
+
+    #[test]
+    fn test_minimize_presses_rejects_mismatched_lengths() {
+        assert_eq!(
+            LPBuilder::minimize_presses(&[vec![1, 1], vec![0, 1]], &[3]),
+            Err(LPError::RhsLength {
+                constraints: 2,
+                rhs: 1
+            })
+        );
+    }
 }
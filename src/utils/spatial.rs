@@ -0,0 +1,159 @@
+//! Spatial indexes for nearest-neighbour queries over point clouds.
+
+use crate::utils::point::Point3;
+use std::collections::BinaryHeap;
+
+/// A static k-d tree over [`Point3`]s, answering nearest-neighbour queries by squared
+/// Euclidean distance in O(log n) on average rather than a full scan.
+///
+/// The tree is implicit: `points` is arranged so the median of each range is the node
+/// splitting that range, on an axis that cycles x, y, z with depth.
+#[derive(Debug, Clone)]
+pub struct KdTree3 {
+    points: Vec<Point3>,
+}
+
+impl KdTree3 {
+    pub fn new(mut points: Vec<Point3>) -> Self {
+        build(&mut points, 0);
+        Self { points }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The stored point closest to `target`, or `None` if the tree is empty. Ties are
+    /// broken arbitrarily.
+    pub fn nearest(&self, target: Point3) -> Option<Point3> {
+        self.k_nearest(target, 1).pop()
+    }
+
+    /// Up to `k` stored points closest to `target`, nearest first.
+    pub fn k_nearest(&self, target: Point3, k: usize) -> Vec<Point3> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        search(&self.points, 0, target, k, &mut best);
+        best.into_sorted_vec().into_iter().map(|(_, p)| p).collect()
+    }
+}
+
+/// Keeps the `k` closest points seen so far in `best`, a max-heap by distance.
+fn search(
+    points: &[Point3],
+    depth: usize,
+    target: Point3,
+    k: usize,
+    best: &mut BinaryHeap<(u64, Point3)>,
+) {
+    if points.is_empty() {
+        return;
+    }
+    let mid = points.len() / 2;
+    let node = points[mid];
+    best.push((dist_squared(node, target), node));
+    if best.len() > k {
+        best.pop();
+    }
+
+    let diff = i64::from(axis(target, depth)) - i64::from(axis(node, depth));
+    let (near, far) = if diff < 0 {
+        (&points[..mid], &points[mid + 1..])
+    } else {
+        (&points[mid + 1..], &points[..mid])
+    };
+    search(near, depth + 1, target, k, best);
+    // The far side can only help if the splitting plane is closer than the worst kept
+    let plane = diff.unsigned_abs().pow(2);
+    if best.len() < k || best.peek().is_some_and(|&(worst, _)| plane < worst) {
+        search(far, depth + 1, target, k, best);
+    }
+}
+
+fn build(points: &mut [Point3], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+    let mid = points.len() / 2;
+    points.select_nth_unstable_by_key(mid, |&p| axis(p, depth));
+    let (left, right) = points.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+fn axis(p: Point3, depth: usize) -> i32 {
+    match depth % 3 {
+        0 => p.x,
+        1 => p.y,
+        _ => p.z,
+    }
+}
+
+fn dist_squared(a: Point3, b: Point3) -> u64 {
+    [(a.x, b.x), (a.y, b.y), (a.z, b.z)]
+        .into_iter()
+        .map(|(u, v)| (i64::from(u) - i64::from(v)).unsigned_abs().pow(2))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small deterministic LCG, so the "random" cloud is the same on every run.
+    fn cloud(n: usize, seed: u64) -> Vec<Point3> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % 1000) as i32 - 500
+        };
+        (0..n)
+            .map(|_| Point3::new(next(), next(), next()))
+            .collect()
+    }
+
+    #[test]
+    fn test_nearest_matches_brute_force() {
+        let points = cloud(200, 7);
+        let tree = KdTree3::new(points.clone());
+        assert_eq!(tree.len(), 200);
+        for target in cloud(50, 99) {
+            let brute = points.iter().map(|&p| dist_squared(p, target)).min();
+            let found = tree.nearest(target).map(|p| dist_squared(p, target));
+            assert_eq!(found, brute);
+        }
+    }
+
+    #[test]
+    fn test_k_nearest_matches_brute_force() {
+        let points = cloud(100, 3);
+        let tree = KdTree3::new(points.clone());
+        let target = Point3::new(10, -20, 30);
+
+        let mut brute: Vec<_> = points.iter().map(|&p| dist_squared(p, target)).collect();
+        brute.sort();
+        let found: Vec<_> = tree
+            .k_nearest(target, 5)
+            .into_iter()
+            .map(|p| dist_squared(p, target))
+            .collect();
+        assert_eq!(found, brute[..5]);
+        assert_eq!(tree.k_nearest(target, 500).len(), 100);
+        assert!(tree.k_nearest(target, 0).is_empty());
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let tree = KdTree3::new(Vec::new());
+        assert!(tree.is_empty());
+        assert_eq!(tree.nearest(Point3::new(0, 0, 0)), None);
+    }
+}
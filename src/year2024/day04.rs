@@ -50,9 +50,9 @@ fn part2(input: &Grid<char>) -> usize {
     let mut num_occurrences = 0;
 
     // Filter for 'A' positions that have room for a 3x3 X-shape
-    let start_positions = input.all_positions(|&c| c == 'A').filter(|&p| {
-        p.x > 0 && p.x < (input.width as i32 - 1) && p.y > 0 && p.y < (input.height as i32 - 1)
-    });
+    let start_positions = input
+        .all_positions(|&c| c == 'A')
+        .filter(|&p| !input.is_border(p));
 
     for p in start_positions {
         // Define relative corners using Point addition/subtraction
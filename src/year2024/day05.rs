@@ -2,6 +2,7 @@
 //!
 //! Link: <https://adventofcode.com/2024/day/5>
 
+use crate::utils::read_lines::two_blocks;
 use anyhow::{Error, Result};
 use itertools::Itertools;
 use std::cmp::Ordering;
@@ -81,7 +82,7 @@ impl FromStr for Input {
     type Err = Error;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (orderings, updates) = input.split_once("\n\n").unwrap();
+        let (orderings, updates) = two_blocks(input)?;
         let mut ordering: HashMap<usize, HashSet<usize>> = HashMap::new();
 
         for comparison in orderings.lines() {
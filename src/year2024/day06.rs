@@ -2,9 +2,9 @@
 //!
 //! Link: <https://adventofcode.com/2024/day/6>
 
-use crate::utils::grid::Grid;
+use crate::utils::grid::{Grid, TraceResult};
 use crate::utils::point::Point;
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use rayon::prelude::*;
 use std::collections::HashSet;
 use std::str::FromStr;
@@ -12,78 +12,41 @@ use std::str::FromStr;
 pub fn main(input_data: &str) -> Result<(usize, usize)> {
     let input = Grid::<char>::from_str(input_data)?;
 
-    Ok((part1(&input), part2(&input)))
+    Ok((part1(&input)?, part2(&input)?))
 }
 
-fn part1(input: &Grid<char>) -> usize {
-    let start_pos = input.find_pos(|&c| c == '^').expect("Guard not found");
-    get_visited_locations(input, start_pos).len()
+fn part1(input: &Grid<char>) -> Result<usize> {
+    let start_pos = find_guard(input)?;
+    Ok(get_visited_locations(input, start_pos)?.len())
 }
 
-/// Simulates the guard's path and returns all visited unique positions.
-fn get_visited_locations(input: &Grid<char>, start_pos: Point) -> HashSet<Point> {
-    let mut visited = HashSet::new();
-    let mut curr = start_pos;
-    let mut dir = Point::UP; // North
-
-    loop {
-        visited.insert(curr);
-        let next = curr + dir;
-
-        if !input.in_bounds(next) {
-            break;
-        }
-
-        if input[next] == '#' {
-            dir = dir.rotate_right_90();
-        } else {
-            curr = next;
-        }
-    }
-    visited
+fn find_guard(input: &Grid<char>) -> Result<Point> {
+    input.find_pos(|&c| c == '^').context("Guard not found")
 }
 
-/// Checks if placing an obstacle at `extra_obstacle` causes the guard to loop.
-fn check_does_loop(input: &Grid<char>, start_pos: Point, extra_obstacle: Point) -> bool {
-    // We use a simple 2D array or a BitSet for even more speed,
-    // but HashSet of (Point, Direction) is robust.
-    let mut seen = HashSet::new();
-    let mut curr = start_pos;
-    let mut dir = Point::UP;
-
-    loop {
-        // If we've been at this position facing this way before, it's a loop
-        if !seen.insert((curr, dir)) {
-            return true;
-        }
-
-        let next = curr + dir;
-
-        if !input.in_bounds(next) {
-            return false;
-        }
-
-        // Check original obstacles OR the new one we're testing
-        if input[next] == '#' || next == extra_obstacle {
-            dir = dir.rotate_right_90();
-        } else {
-            curr = next;
-        }
+/// Simulates the guard's path and returns all visited unique positions.
+fn get_visited_locations(input: &Grid<char>, start_pos: Point) -> Result<HashSet<Point>> {
+    match input.trace_with_turns(start_pos, Point::UP, |_, &c| c == '#') {
+        TraceResult::Exited(visited) => Ok(visited),
+        TraceResult::Loop => bail!("Guard never leaves the lab"),
     }
 }
 
-fn part2(input: &Grid<char>) -> usize {
-    let start_pos = input.find_pos(|&c| c == '^').expect("Guard not found");
+fn part2(input: &Grid<char>) -> Result<usize> {
+    let start_pos = find_guard(input)?;
 
     // Optimization: Only test positions that the guard actually visits in Part 1.
     // An obstacle elsewhere cannot possibly affect the path.
-    let original_path = get_visited_locations(input, start_pos);
+    let original_path = get_visited_locations(input, start_pos)?;
 
-    original_path
+    Ok(original_path
         .into_par_iter()
         .filter(|&pos| pos != start_pos)
-        .filter(|&pos| check_does_loop(input, start_pos, pos))
-        .count()
+        .filter(|&pos| {
+            input.trace_with_turns(start_pos, Point::UP, |p, &c| c == '#' || p == pos)
+                == TraceResult::Loop
+        })
+        .count())
 }
 
 #[cfg(test)]
@@ -106,12 +69,27 @@ mod tests {
     #[test]
     fn test_part1() {
         let input = Grid::<char>::from_str(EXAMPLE).unwrap();
-        assert_eq!(part1(&input), 41);
+        assert_eq!(part1(&input).unwrap(), 41);
+    }
+
+    #[test]
+    fn test_part2_obstacle_causes_loop() {
+        let mut input = Grid::<char>::from_str(EXAMPLE).unwrap();
+        input[Point::new(3, 6)] = '#';
+        let looped = input.trace_with_turns(Point::new(4, 6), Point::UP, |_, &c| c == '#');
+        assert_eq!(looped, TraceResult::Loop);
     }
 
     #[test]
     fn test_part2() {
         let input = Grid::<char>::from_str(EXAMPLE).unwrap();
-        assert_eq!(part2(&input), 6);
+        assert_eq!(part2(&input).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_looping_guard_is_an_error() {
+        let input = Grid::<char>::from_str(".#..\n...#\n#^..\n..#.").unwrap();
+        assert!(part1(&input).is_err());
+        assert!(part2(&input).is_err());
     }
 }
@@ -3,8 +3,8 @@
 //! Link: <https://adventofcode.com/2024/day/7>
 
 use anyhow::Result;
-use std::collections::VecDeque;
 use std::str::FromStr;
+
 pub fn main(input_data: &str) -> Result<(usize, usize)> {
     let input = parse_input(input_data)?;
     Ok((part1(&input), part2(&input)))
@@ -15,43 +15,66 @@ fn parse_input(input_data: &str) -> Result<Vec<Calibration>> {
 }
 
 fn part1(input: &[Calibration]) -> usize {
-    let mut acc = 0;
-    for calibration in input {
-        let mut queue = VecDeque::from([calibration.equation[0]]);
-        for value in calibration.equation.iter().skip(1) {
-            let mut next_vals = Vec::new();
-            while let Some(val) = queue.pop_front() {
-                next_vals.push(val + value);
-                next_vals.push(val * value);
-            }
-            queue.extend(next_vals);
-        }
-        if queue.iter().any(|&x| x == calibration.value) {
-            acc += calibration.value;
-        }
-    }
-    acc
+    total_calibration(input, &[Op::Add, Op::Mul])
 }
 
 fn part2(input: &[Calibration]) -> usize {
-    let mut acc = 0;
-    for calibration in input {
-        let mut queue = VecDeque::from([calibration.equation[0]]);
-        for value in calibration.equation.iter().skip(1) {
-            let mut next_vals = Vec::new();
-            while let Some(val) = queue.pop_front() {
-                next_vals.push(val + value);
-                next_vals.push(val * value);
-                let concat = val.to_string() + &value.to_string();
-                next_vals.push(concat.parse::<usize>().unwrap());
-            }
-            queue.extend(next_vals);
-        }
-        if queue.iter().any(|&x| x == calibration.value) {
-            acc += calibration.value;
+    total_calibration(input, &[Op::Add, Op::Mul, Op::Concat])
+}
+
+fn total_calibration(input: &[Calibration], ops: &[Op]) -> usize {
+    input
+        .iter()
+        .filter(|c| evaluate(c.value, &c.equation, ops))
+        .map(|c| c.value)
+        .sum()
+}
+
+/// Whether inserting operators from `ops` between the numbers of `equation`, evaluated
+/// left to right, can produce `target`.
+///
+/// Every operator is non-decreasing on positive operands, so a partial result that
+/// already exceeds `target` is pruned unless a `0` remains to multiply it back down.
+fn evaluate(target: usize, equation: &[usize], ops: &[Op]) -> bool {
+    match equation.split_first() {
+        Some((&first, rest)) => reachable(target, first, rest, ops),
+        None => false,
+    }
+}
+
+fn reachable(target: usize, acc: usize, rest: &[usize], ops: &[Op]) -> bool {
+    if acc > target && !rest.contains(&0) {
+        return false;
+    }
+    match rest.split_first() {
+        None => acc == target,
+        Some((&value, rest)) => ops.iter().any(|op| {
+            op.apply(acc, value)
+                .is_some_and(|next| reachable(target, next, rest, ops))
+        }),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Mul,
+    /// Appends the digits of the right operand to the left, e.g. `12 || 345 = 12345`.
+    Concat,
+}
+
+impl Op {
+    /// Applies the operator, returning `None` on overflow.
+    fn apply(self, a: usize, b: usize) -> Option<usize> {
+        match self {
+            Op::Add => a.checked_add(b),
+            Op::Mul => a.checked_mul(b),
+            Op::Concat => 10usize
+                .checked_pow(b.checked_ilog10().unwrap_or(0) + 1)
+                .and_then(|shift| a.checked_mul(shift))
+                .and_then(|shifted| shifted.checked_add(b)),
         }
     }
-    acc
 }
 
 struct Calibration {
@@ -102,4 +125,35 @@ mod tests {
         let input = parse_input(EXAMPLE).unwrap();
         assert_eq!(part2(&input), 11387);
     }
+
+    #[test]
+    fn test_evaluate() {
+        let two = [Op::Add, Op::Mul];
+        let three = [Op::Add, Op::Mul, Op::Concat];
+        assert!(evaluate(3267, &[81, 40, 27], &two));
+        assert!(!evaluate(156, &[15, 6], &two));
+        assert!(evaluate(156, &[15, 6], &three));
+        assert!(evaluate(7290, &[6, 8, 6, 15], &three));
+        assert!(!evaluate(83, &[17, 5], &three));
+        assert_eq!(Op::Concat.apply(12, 0), Some(120));
+        assert_eq!(Op::Concat.apply(usize::MAX / 10, 99), None);
+    }
+
+    #[test]
+    fn test_evaluate_long_equation() {
+        // 3^39 operator combinations, but every branch past the target is pruned
+        let equation = [2; 40];
+        let three = [Op::Add, Op::Mul, Op::Concat];
+        assert!(evaluate(80, &equation, &three));
+        assert!(!evaluate(81, &equation, &three));
+        // 2 || 2, then add the remaining 38 twos
+        assert!(evaluate(22 + 2 * 38, &equation, &three));
+    }
+
+    #[test]
+    fn test_zero_operand_can_reduce() {
+        assert!(evaluate(0, &[5, 0], &[Op::Add, Op::Mul]));
+        assert!(evaluate(3, &[5, 0, 3], &[Op::Add, Op::Mul]));
+        assert!(!evaluate(3, &[5, 1, 3], &[Op::Add, Op::Mul]));
+    }
 }
@@ -68,13 +68,9 @@ fn part2(grid: &Grid<char>) -> usize {
 
 /// Groups antenna positions by their frequency (character).
 fn get_antenna_positions(grid: &Grid<char>) -> HashMap<char, Vec<Point>> {
-    let mut map: HashMap<char, Vec<Point>> = HashMap::new();
-
-    // Use our grid utility to find all antennas (non-dots)
-    for pos in grid.all_positions(|&c| c != '.') {
-        map.entry(grid[pos]).or_default().push(pos);
-    }
-
+    let mut map = grid.index_by_value();
+    // Empty ground is not an antenna
+    map.remove(&'.');
     map
 }
 
@@ -5,66 +5,53 @@
 
 use crate::utils::grid::Grid;
 use crate::utils::point::Point;
-use anyhow::Result;
-use rayon::prelude::*;
+use anyhow::{Context, Result};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::FromStr;
 
 pub fn main(input_data: &str) -> Result<(usize, usize)> {
-    // 1. Initial parse into Grid<char>
-    let char_grid = Grid::<char>::from_str(input_data)?;
-
-    // 2. Optimization: Convert to Grid<u8> immediately to avoid char-to-digit
-    // conversions in the performance-critical path.
-    let grid = Grid::from_vals(
-        char_grid
-            .g
-            .iter()
-            .map(|&c| c.to_digit(10).unwrap_or(99) as u8)
-            .collect(),
-        char_grid.width,
-        char_grid.height,
-    );
+    // Work on Grid<u8> to avoid char-to-digit conversions in the performance-critical path
+    let grid = Grid::<char>::from_str(input_data)?
+        .as_digits()
+        .context("Trail map must only contain digits")?;
 
     Ok((part1(&grid), part2(&grid)))
 }
 
 /// Part 1: Score is the number of '9' height peaks reachable from each trailhead.
 fn part1(grid: &Grid<u8>) -> usize {
-    grid.all_positions(|&h| h == 0)
-        .par_bridge()
-        .map(|start| {
-            let mut queue = VecDeque::from([start]);
-            let mut visited = HashSet::from([start]);
-            let mut reachable_peaks = 0;
-
-            while let Some(curr) = queue.pop_front() {
-                if grid[curr] == 9 {
-                    reachable_peaks += 1;
-                    continue;
-                }
+    grid.par_for_each_start(grid.all_positions(|&h| h == 0), |start| {
+        let mut queue = VecDeque::from([start]);
+        let mut visited = HashSet::from([start]);
+        let mut reachable_peaks = 0;
+
+        while let Some(curr) = queue.pop_front() {
+            if grid[curr] == 9 {
+                reachable_peaks += 1;
+                continue;
+            }
 
-                for next in grid.cardinal_neighbors(curr) {
-                    // Gradual incline: next step must be exactly current + 1
-                    if grid[next] == grid[curr] + 1 && visited.insert(next) {
-                        queue.push_back(next);
-                    }
+            for next in grid.cardinal_neighbors(curr) {
+                // Gradual incline: next step must be exactly current + 1
+                if grid[next] == grid[curr] + 1 && visited.insert(next) {
+                    queue.push_back(next);
                 }
             }
-            reachable_peaks
-        })
-        .sum()
+        }
+        reachable_peaks
+    })
+    .into_iter()
+    .sum()
 }
 
 /// Part 2: Rating is the number of distinct hiking trails (unique paths) to any '9'.
 fn part2(grid: &Grid<u8>) -> usize {
-    grid.all_positions(|&h| h == 0)
-        .par_bridge()
-        .map(|start| {
-            let mut memo = HashMap::new();
-            count_trails_memo(grid, start, &mut memo)
-        })
-        .sum()
+    grid.par_for_each_start(grid.all_positions(|&h| h == 0), |start| {
+        let mut memo = HashMap::new();
+        count_trails_memo(grid, start, &mut memo)
+    })
+    .into_iter()
+    .sum()
 }
 
 /// A recursive DFS with memoization to count all unique paths from `curr` to any peak.
@@ -109,16 +96,10 @@ mod tests {
 
     #[test]
     fn test_day10_full() {
-        let char_grid = Grid::<char>::from_str(EXAMPLE).unwrap();
-        let grid = Grid::from_vals(
-            char_grid
-                .g
-                .iter()
-                .map(|&c| c.to_digit(10).unwrap_or(99) as u8)
-                .collect(),
-            char_grid.width,
-            char_grid.height,
-        );
+        let grid = Grid::<char>::from_str(EXAMPLE)
+            .unwrap()
+            .as_digits()
+            .unwrap();
         assert_eq!(part1(&grid), 36);
         assert_eq!(part2(&grid), 81);
     }
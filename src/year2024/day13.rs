@@ -2,6 +2,7 @@
 //!
 //! Link: <https://adventofcode.com/2024/day/13>
 
+use crate::utils::numbers::solve_2x2;
 use crate::utils::simplex::{LPBuilder, LPOps, branch_and_bound};
 use anyhow::{Error, Result};
 use regex::Regex;
@@ -17,29 +18,20 @@ fn parse_input(input: &str) -> Result<Vec<ClawMachine>> {
 }
 
 fn part1(input: &[ClawMachine]) -> i64 {
-    let mut acc = 0;
-    for claw_machine in input {
-        match branch_and_bound(claw_machine.to_part1_lp(), 2) {
-            Some(solution) => {
-                acc += solution;
-            }
-            None => continue,
-        }
-    }
-    acc
+    input
+        .iter()
+        .filter_map(|m| m.min_tokens(m.prize, |presses| presses <= 100, || m.to_part1_lp()))
+        .sum()
 }
 
 fn part2(input: &[ClawMachine]) -> i64 {
-    let mut acc = 0;
-    for claw_machine in input {
-        match branch_and_bound(claw_machine.to_part2_lp(), 2) {
-            Some(solution) => {
-                acc += solution;
-            }
-            None => continue,
-        }
-    }
-    acc
+    input
+        .iter()
+        .filter_map(|m| {
+            let prize = m.prize.map(|p| p + 10000000000000);
+            m.min_tokens(prize, |presses| presses >= 100, || m.to_part2_lp())
+        })
+        .sum()
 }
 
 #[derive(Debug)]
@@ -50,6 +42,26 @@ struct ClawMachine {
 }
 
 impl ClawMachine {
+    /// Cheapest way to win `prize`, where each press count must satisfy `presses_ok`.
+    ///
+    /// Independent buttons give a unique solution, found directly with Cramer's rule;
+    /// only parallel buttons fall back to solving `lp` with branch and bound.
+    fn min_tokens(
+        &self,
+        prize: [i64; 2],
+        presses_ok: impl Fn(i64) -> bool,
+        lp: impl FnOnce() -> LPBuilder,
+    ) -> Option<i64> {
+        let [a, b] = [self.button_a, self.button_b];
+        if a[0] * b[1] == a[1] * b[0] {
+            return branch_and_bound(lp(), 2, None)
+                .expect("claw machine programs always have two variables")
+                .map(|(tokens, _)| tokens);
+        }
+        let (x, y) = solve_2x2(a, b, prize)?;
+        (x >= 0 && y >= 0 && presses_ok(x) && presses_ok(y)).then_some(3 * x + y)
+    }
+
     fn to_part1_lp(&self) -> LPBuilder {
         let mut builder = LPBuilder::new();
 
@@ -3,6 +3,7 @@
 //! Link: <https://adventofcode.com/2024/day/15>
 use crate::utils::grid::Grid;
 use crate::utils::point::Point;
+use crate::utils::read_lines::two_blocks;
 use anyhow::{Error, Result};
 use std::collections::HashSet;
 use std::fmt::{self, Display};
@@ -20,35 +21,18 @@ fn part1(input: &Input) -> i32 {
         .expect("No robot found");
 
     for &dir in &input.moves {
-        let delta = dir.to_point();
-        let next = robot + delta;
-
-        match grid[next] {
-            Element::Empty => {
-                grid[next] = Element::Robot;
-                grid[robot] = Element::Empty;
-                robot = next;
-            }
-            Element::Wall => continue,
-            Element::Box => {
-                let mut scan = next;
-                while grid[scan] == Element::Box {
-                    scan = scan + delta;
-                }
-                if grid[scan] == Element::Empty {
-                    // Standard shift: move the whole line of boxes
-                    grid[scan] = Element::Box;
-                    grid[next] = Element::Robot;
-                    grid[robot] = Element::Empty;
-                    robot = next;
-                }
-            }
-            _ => unreachable!(),
-        }
+        robot = grid.push(
+            robot,
+            dir.to_point(),
+            |&el| el == Element::Wall,
+            |&el| el == Element::Box,
+            Element::Empty,
+        );
     }
     score(&grid, Element::Box)
 }
 
+#[allow(clippy::assign_op_pattern)]
 fn part2(input: &Input) -> i32 {
     let mut grid = expand_grid(&input.grid);
     let mut robot = grid
@@ -121,8 +105,8 @@ fn score(grid: &Grid<Element>, target: Element) -> i32 {
 }
 
 fn expand_grid(old: &Grid<Element>) -> Grid<Element> {
-    let mut new_vec = Vec::with_capacity(old.g.len() * 2);
-    for cell in &old.g {
+    let mut new_vec = Vec::with_capacity(old.width * old.height * 2);
+    for cell in old {
         match cell {
             Element::Wall => { new_vec.push(Element::Wall); new_vec.push(Element::Wall); }
             Element::Box => { new_vec.push(Element::BoxLeft); new_vec.push(Element::BoxRight); }
@@ -147,6 +131,21 @@ impl Display for Element {
     }
 }
 
+impl TryFrom<char> for Element {
+    type Error = Error;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Ok(match c {
+            '#' => Self::Wall,
+            '.' => Self::Empty,
+            '@' => Self::Robot,
+            'O' => Self::Box,
+            '[' => Self::BoxLeft,
+            ']' => Self::BoxRight,
+            _ => return Err(Error::msg("Unknown map element")),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Direction { Up, Down, Left, Right }
 
@@ -169,25 +168,9 @@ struct Input {
 impl FromStr for Input {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (g_str, m_str) = s.split_once("\n\n").ok_or_else(|| Error::msg("Invalid input"))?;
-
-        // Parse grid manually to convert chars to Elements
-        let lines: Vec<&str> = g_str.lines().collect();
-        let height = lines.len();
-        let width = lines[0].len();
-        let mut g = Vec::with_capacity(width * height);
-        for line in lines {
-            for c in line.chars() {
-                g.push(match c {
-                    '#' => Element::Wall,
-                    'O' => Element::Box,
-                    '@' => Element::Robot,
-                    '[' => Element::BoxLeft,
-                    ']' => Element::BoxRight,
-                    _ => Element::Empty,
-                });
-            }
-        }
+        let (g_str, m_str) = two_blocks(s)?;
+
+        let grid = Grid::try_parse_with(g_str, Element::try_from)?;
 
         let moves = m_str.chars()
             .filter(|c| !c.is_whitespace())
@@ -198,7 +181,7 @@ impl FromStr for Input {
                 _ => Direction::Right,
             }).collect();
 
-        Ok(Self { grid: Grid::from_vals(g, width, height), moves })
+        Ok(Self { grid, moves })
     }
 }
 
@@ -278,6 +261,12 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
         );
     }
 
+    #[test]
+    fn test_input_parsing_rejects_unknown_element() {
+        let input = Input::from_str("###\n#@?\n###\n\n<>");
+        assert!(input.is_err());
+    }
+
     #[test]
     fn test_part1_small_example() {
         let input = Input::from_str(SMALL_EXAMPLE).unwrap();
@@ -289,4 +278,10 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
         let input = Input::from_str(LARGE_EXAMPLE).unwrap();
         assert_eq!(part1(&input), 10092);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_part2_large_example() {
+        let input = Input::from_str(LARGE_EXAMPLE).unwrap();
+        assert_eq!(part2(&input), 9021);
+    }
+}
@@ -1,6 +1,7 @@
 //! Advent of Code 2025 Day 1
 //! Link: <https://adventofcode.com/2025/day/3>
 //!
+use crate::utils::numbers::modular_position;
 use anyhow::Result;
 use std::str::FromStr;
 
@@ -24,18 +25,14 @@ pub fn solve(input: &[Command]) -> Input {
     let mut part2 = 0;
 
     for cmd in input {
-        match cmd {
-            Command::Left(d) => {
-                let reversed = (100 - dial) % 100;
-                part2 += (reversed + d) / 100;
-                dial = (dial - d).rem_euclid(100);
-            }
-            Command::Right(d) => {
-                part2 += (dial + d) / 100;
-                dial = (dial + d) % 100;
-            }
-        }
+        let step = match *cmd {
+            Command::Left(d) => (false, d),
+            Command::Right(d) => (true, d),
+        };
+        let (pos, zero_hits) = modular_position(dial, &[step], 100);
+        dial = pos;
         part1 += i64::from(dial == 0);
+        part2 += zero_hits;
     }
     (part1, part2)
 }
@@ -1,10 +1,12 @@
 //! Advent of Code 2025 Day 3
 //! Link: <https://adventofcode.com/2025/day/3>
 //!
+use crate::utils::iter::window_argmax;
+use crate::utils::read_lines::digit_rows;
 use anyhow::Result;
 
 pub fn main(data: &str) -> Result<(u64, u64)> {
-    let input = parse_input(data).unwrap();
+    let input = parse_input(data)?;
     let (part1, part2) = solve(input);
 
     Ok((part1, part2))
@@ -17,20 +19,8 @@ pub fn solve(input: Vec<Vec<u32>>) -> (u64, u64) {
     (part1, part2)
 }
 
-type NestedU32 = Vec<Vec<u32>>;
-
-pub fn parse_input(input: &str) -> Result<Vec<Vec<u32>>, String> {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| {
-                    c.to_digit(10)
-                        .ok_or_else(|| format!("Parsing error: Invalid digit '{}' found.", c))
-                })
-                .collect::<Result<Vec<u32>, String>>()
-        })
-        .collect::<Result<NestedU32, String>>()
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u32>>> {
+    digit_rows(input)
 }
 
 pub fn part1(input: &[Vec<u32>]) -> u64 {
@@ -48,19 +38,10 @@ pub fn max_joltage<const DIGITS: usize>(input: &[Vec<u32>]) -> u64 {
         let mut acc = 0;
         for remaining_digits in (0..DIGITS).rev() {
             let end = bank.len() - remaining_digits;
-
-            let slice = &bank[start..end];
-            let (mut max_idx, mut max_digit) = (0, 0);
-
-            for (idx, &digit) in slice.iter().enumerate() {
-                if digit > max_digit {
-                    max_idx = idx;
-                    max_digit = digit;
-                }
-            }
+            let (max_idx, max_digit) = window_argmax(bank, start, end);
 
             acc = acc * 10 + u64::from(max_digit);
-            start += max_idx + 1;
+            start = max_idx + 1;
         }
         answer += acc;
     }
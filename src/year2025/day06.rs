@@ -1,39 +1,25 @@
 //! Advent of Code 2025 Day 6
 //! Link: <https://adventofcode.com/2025/day/6>
 //!
+use crate::utils::grid::Grid;
+use crate::utils::iter::split_positions;
 use anyhow::Result;
 
 pub fn main(data: &str) -> Result<(u64, u64)> {
     let input = parse_input(data)?;
 
-    Ok((part1(&input), part2(&input)))
+    Ok((part1(&input), part2(&input)?))
 }
 
 pub fn parse_input(input: &str) -> Result<Vec<Calculation>> {
-    let _max_length = input.lines().map(|line| line.len()).max().unwrap();
-    let mut data = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    for row in &mut data {
-        if row.len() < _max_length {
-            row.extend(vec![' '; _max_length - row.len()]);
-        }
-    }
+    let grid = Grid::from_lines_padded(input.lines(), ' ');
+    let data = grid.g.chunks(grid.width).collect::<Vec<_>>();
 
-    let mut operator_idx = data
-        .last()
-        .unwrap()
-        .iter()
-        .enumerate()
-        .filter(|&(_, &op)| op == '+' || op == '*')
-        .map(|(idx, _)| idx)
-        .collect::<Vec<_>>();
-    operator_idx.push(_max_length + 1);
+    // Each span also includes the blank separator column, which parsing ignores
+    let spans = split_positions(data.last().unwrap(), |&op| op == '+' || op == '*');
 
     let mut calculations = Vec::new();
-    for w in operator_idx.windows(2).map(|w| (w[0], w[1] - 1)) {
+    for w in spans {
         let mut numbers = Vec::new();
         for row in &data {
             numbers.push(row[w.0..w.1].to_vec());
@@ -70,24 +56,22 @@ pub fn part1(input: &[Calculation]) -> u64 {
     acc
 }
 
-pub fn part2(_input: &[Calculation]) -> u64 {
+pub fn part2(_input: &[Calculation]) -> Result<u64> {
     let mut acc = 0;
     for calculation in _input {
-        let mut terms: Vec<u64> = Vec::new();
-        for c in (0..calculation.numbers[0].len()).rev() {
-            let term = String::from_iter(calculation.numbers.iter().map(|row| row[c].to_string()))
-                .trim()
-                .parse::<u64>()
-                .unwrap();
-            terms.push(term);
-        }
+        let terms = Grid::from_vals(
+            calculation.numbers.concat(),
+            calculation.numbers[0].len(),
+            calculation.numbers.len(),
+        )
+        .columns_as_numbers()?;
 
         acc += match calculation.operation {
             Operation::Add => terms.iter().sum::<u64>(),
             Operation::Multiply => terms.iter().product::<u64>(),
         }
     }
-    acc
+    Ok(acc)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -122,6 +106,6 @@ mod tests {
     #[test]
     fn test_part2() {
         let input = parse_input(EXAMPLE).unwrap();
-        assert_eq!(part2(&input), 3263827);
+        assert_eq!(part2(&input).unwrap(), 3263827);
     }
 }
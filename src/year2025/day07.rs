@@ -1,6 +1,7 @@
 //! Advent of Code 2025 Day 7
 //! Link: <https://adventofcode.com/2025/day/7>
 //!
+use crate::utils::read_lines::char_rows;
 use anyhow::Result;
 use std::collections::HashSet;
 
@@ -13,10 +14,7 @@ pub fn main(data: &str) -> Result<(u32, u64)> {
 }
 
 pub fn parse_input(input: &str) -> Result<Vec<Vec<char>>> {
-    Ok(input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect())
+    Ok(char_rows(input))
 }
 
 /// For this implementation we can perform a simple DFS of the manifold.
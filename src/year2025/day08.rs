@@ -2,12 +2,14 @@
 //! Link: <https://adventofcode.com/2025/day/8>
 
 use crate::utils::disjointset::DisjointSet;
+use crate::utils::graph::{k_closest_pairs, kruskal};
 use anyhow::{Context, Error, Result};
 use std::str::FromStr;
 
 pub fn main(data: &str) -> Result<(usize, usize)> {
     let input = parse_input(data)?;
-    Ok((part1(&input), part2(&input)))
+    let part2 = part2(&input).context("need at least two points")?;
+    Ok((part1(&input), part2))
 }
 
 pub fn parse_input(input: &str) -> Result<Input> {
@@ -19,10 +21,11 @@ pub fn part1(input: &Input) -> usize {
     let mut ds = DisjointSet::new(n);
 
     // Construct DSU from the top N closest pairs
-    let nearest_neighbours = get_closest_pairs(&input.points);
-    for (i, j) in nearest_neighbours.into_iter().take(input.num_pairs) {
-        ds.union(i as usize, j as usize);
-    }
+    let points = &input.points;
+    let nearest_neighbours = k_closest_pairs(n, input.num_pairs, |i, j| {
+        points[i].euclidean_squared(points[j])
+    });
+    ds.union_all(nearest_neighbours.into_iter().map(|(i, j, _)| (i, j)));
 
     // To find the top 3 largest sets:
     // Identify all roots and collect their sizes
@@ -39,41 +42,23 @@ pub fn part1(input: &Input) -> usize {
 }
 
 // Kruskal's Algorithm for Minimum Spanning Tree
-pub fn part2(input: &Input) -> usize {
-    let n = input.points.len();
-    let mut ds = DisjointSet::new(n);
-    let mut last_edge = (0, 0);
-
-    // Kruskal's: Iterate through edges sorted by weight
-    for (u, v) in get_closest_pairs(&input.points) {
-        let u = u as usize;
-        let v = v as usize;
-        if ds.union(u, v) {
-            last_edge = (u, v);
-            // If we've reduced the forest to a single tree, we're done
-            if ds.num_sets == 1 {
-                break;
-            }
-        }
-    }
+pub fn part2(input: &Input) -> Option<usize> {
+    let mst = kruskal(input.points.len(), &pair_distances(&input.points));
+    // The last edge added is the one that joins everything into a single circuit
+    let &(u, v, _) = mst.last()?;
 
-    input.points[last_edge.0].x * input.points[last_edge.1].x
+    Some(input.points[u].x * input.points[v].x)
 }
 
-type NearestNeighbour = (u16, u16);
-
-fn get_closest_pairs(points: &[Point]) -> Vec<NearestNeighbour> {
-    let mut distances: Vec<(usize, u16, u16)> = Vec::new();
+/// Every pair of points with the squared distance between them.
+fn pair_distances(points: &[Point]) -> Vec<(usize, usize, u64)> {
+    let mut distances = Vec::new();
     for (i, p1) in points.iter().enumerate() {
         for (j, p2) in points.iter().enumerate().skip(i + 1) {
-            let distance = p1.euclidean_distance(*p2);
-            distances.push((distance, i as u16, j as u16));
+            distances.push((i, j, p1.euclidean_squared(*p2)));
         }
     }
-    // Sort by distance ascending
-    distances.sort_unstable_by_key(|(dist, _, _)| *dist);
-
-    distances.into_iter().map(|(_, i, j)| (i, j)).collect()
+    distances
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -84,10 +69,11 @@ pub struct Point {
 }
 
 impl Point {
-    pub fn euclidean_distance(self, other: Self) -> usize {
-        let dx = self.x.abs_diff(other.x);
-        let dy = self.y.abs_diff(other.y);
-        let dz = self.z.abs_diff(other.z);
+    /// Squared Euclidean distance, which orders pairs the same as the true distance.
+    pub fn euclidean_squared(self, other: Self) -> u64 {
+        let dx = self.x.abs_diff(other.x) as u64;
+        let dy = self.y.abs_diff(other.y) as u64;
+        let dz = self.z.abs_diff(other.z) as u64;
         dx * dx + dy * dy + dz * dz
     }
 }
@@ -161,6 +147,27 @@ num_neighbours=10
     #[test]
     fn test_part2() {
         let input = parse_input(EXAMPLE).unwrap();
-        assert_eq!(part2(&input), 25272);
+        assert_eq!(part2(&input), Some(25272));
+    }
+
+    #[test]
+    fn test_part2_needs_two_points() {
+        let input = parse_input("num_neighbours=1\n1,2,3").unwrap();
+        assert_eq!(part2(&input), None);
+        assert!(main("num_neighbours=1\n1,2,3").is_err());
+    }
+
+    #[test]
+    fn test_euclidean_squared_large_coordinates() {
+        let origin = Point { x: 0, y: 0, z: 0 };
+        let far = Point {
+            x: 999,
+            y: 998,
+            z: 997,
+        };
+        let expected = 999 * 999 + 998 * 998 + 997 * 997;
+        assert!(expected > u64::from(u16::MAX));
+        assert_eq!(origin.euclidean_squared(far), expected);
+        assert_eq!(far.euclidean_squared(origin), expected);
     }
 }
@@ -116,29 +116,7 @@ pub fn part2(input: &[Tile]) -> usize {
     }
 
     // 3. 2D Prefix Sum on the Compressed Grid
-    let mut psum = Grid::<usize>::new(0, g.width, g.height);
-    for y in 0..g.height {
-        for x in 0..g.width {
-            let p = Point::new(x as i32, y as i32);
-            let val = g[p] as usize;
-            let left = if x > 0 {
-                psum[Point::new(x as i32 - 1, y as i32)]
-            } else {
-                0
-            };
-            let up = if y > 0 {
-                psum[Point::new(x as i32, y as i32 - 1)]
-            } else {
-                0
-            };
-            let diag = if x > 0 && y > 0 {
-                psum[Point::new(x as i32 - 1, y as i32 - 1)]
-            } else {
-                0
-            };
-            psum[p] = val + left + up - diag;
-        }
-    }
+    let psum = g.prefix_sum();
 
     // 4. Find max area rectangle that is fully "filled" (all true in g)
     let mut max_area = 0;
@@ -152,8 +130,11 @@ pub fn part2(input: &[Tile]) -> usize {
             let (cy1, cy2) = (ycomp.get(y_min), ycomp.get(y_max));
 
             // Query the 2D prefix sum for the compressed region
-            let rect_sum = query_psum(&psum, cx1, cy1, cx2, cy2);
-            let expected_area = (cx2 - cx1 + 1) * (cy2 - cy1 + 1);
+            let rect_sum = psum.rect_sum(
+                Point::new(cx1 as i32, cy1 as i32),
+                Point::new(cx2 as i32, cy2 as i32),
+            );
+            let expected_area = ((cx2 - cx1 + 1) * (cy2 - cy1 + 1)) as u64;
 
             if rect_sum == expected_area {
                 let actual_area = (x_max - x_min + 1) * (y_max - y_min + 1);
@@ -165,25 +146,7 @@ pub fn part2(input: &[Tile]) -> usize {
     max_area
 }
 
-fn query_psum(psum: &Grid<usize>, x1: usize, y1: usize, x2: usize, y2: usize) -> usize {
-    let total = psum[Point::new(x2 as i32, y2 as i32)];
-    let left = if x1 > 0 {
-        psum[Point::new(x1 as i32 - 1, y2 as i32)]
-    } else {
-        0
-    };
-    let up = if y1 > 0 {
-        psum[Point::new(x2 as i32, y1 as i32 - 1)]
-    } else {
-        0
-    };
-    let diag = if x1 > 0 && y1 > 0 {
-        psum[Point::new(x1 as i32 - 1, y1 as i32 - 1)]
-    } else {
-        0
-    };
-    total + diag - left - up
-}
+
 
 #[cfg(test)]
 mod tests {
@@ -1,17 +1,18 @@
 //! Advent of Code 2025 Day 10
 //! Link: <https://adventofcode.com/2025/day/10>
 //!
-use crate::utils::simplex::{LPBuilder, LPOps, branch_and_bound};
+use crate::utils::pathfinding::bfs;
+use crate::utils::simplex::{LPBuilder, branch_and_bound};
 use anyhow::{Context, Error, Result};
+use rayon::prelude::*;
 use regex::Regex;
-use std::collections::{HashSet, VecDeque};
 use std::str::FromStr;
 use std::sync::OnceLock;
 
 pub fn main(data: &str) -> Result<(usize, usize)> {
     let input = parse_input(data)?;
 
-    Ok((part1(&input), part2(&input)))
+    Ok((part1(&input), part2(&input)?))
 }
 
 fn parse_input(input: &str) -> Result<Vec<Input>> {
@@ -22,43 +23,32 @@ fn parse_input(input: &str) -> Result<Vec<Input>> {
 }
 
 fn part1(input: &[Input]) -> usize {
-    let mut acc = 0;
-    // Brute force BFS initial solution
-    for input in input {
-        let mut visited: HashSet<usize> = HashSet::new();
-        let mut queue: VecDeque<(usize, usize)> = VecDeque::from([(0, 0)]);
-
-        while let Some((btn_presses, state)) = queue.pop_front() {
-            if state == input.pattern {
-                acc += btn_presses;
-                break;
-            }
-            for wiring in &input.wiring {
-                let new_state = state ^ wiring;
-                if visited.contains(&new_state) {
-                    continue;
-                }
-                queue.push_back((btn_presses + 1, new_state));
-            }
-            visited.insert(state);
-        }
-    }
-    acc
+    // Each machine is independent, so search them in parallel
+    input.par_iter().filter_map(min_presses).sum()
+}
+
+/// Brute force BFS for the fewest presses that light up `input.pattern`.
+fn min_presses(input: &Input) -> Option<usize> {
+    bfs(
+        0,
+        |&state| state == input.pattern,
+        |&state| input.wiring.iter().map(move |wiring| state ^ wiring),
+    )
 }
 
 // We can recast each problem as ILP and then use the revised simplex algorithm to solve it.
-fn part2(_input: &[Input]) -> usize {
+fn part2(_input: &[Input]) -> Result<usize> {
     let mut acc = 0;
 
     for input in _input {
-        match branch_and_bound(input.lpbuilder.clone(), input.wiring.len()) {
-            Some(solution) => {
+        match branch_and_bound(input.lpbuilder.clone(), input.wiring.len(), None)? {
+            Some((solution, _)) => {
                 acc += solution;
             }
             None => continue,
         }
     }
-    acc as usize
+    Ok(acc as usize)
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -132,11 +122,8 @@ impl FromStr for Input {
             .collect::<Result<Vec<usize>>>()?;
 
         // 4. Build LP
-        let mut lpbuilder = LPBuilder::new();
-        for (i, constraint) in constraints.into_iter().enumerate() {
-            lpbuilder.add_constraint(constraint, LPOps::Eq, joltage_required[i] as i64);
-        }
-        lpbuilder.add_objective(vec![1; num_buttons]);
+        let rhs: Vec<i64> = joltage_required.iter().map(|&j| j as i64).collect();
+        let lpbuilder = LPBuilder::minimize_presses(&constraints, &rhs)?;
 
         Ok(Self {
             pattern,
@@ -150,6 +137,7 @@ impl FromStr for Input {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::simplex::LPOps;
     use pretty_assertions::assert_eq;
 
     const EXAMPLE: &str = "\
@@ -187,9 +175,17 @@ mod tests {
         assert_eq!(part1(&input), 7);
     }
 
+    #[test]
+    fn test_part1_parallel_matches_sequential() {
+        let input = parse_input(EXAMPLE).unwrap();
+        let sequential: usize = input.iter().filter_map(min_presses).sum();
+        assert_eq!(sequential, 7);
+        assert_eq!(part1(&input), sequential);
+    }
+
     #[test]
     fn test_part2() {
         let input = parse_input(EXAMPLE).unwrap();
-        assert_eq!(part2(&input), 33);
+        assert_eq!(part2(&input).unwrap(), 33);
     }
 }
@@ -2,6 +2,7 @@
 //! Link: <https://adventofcode.com/2025/day/12>
 //!
 use crate::utils::grid::Grid;
+use crate::utils::read_lines::labeled_grids;
 use anyhow::{Error, Result};
 use std::str::FromStr;
 
@@ -58,23 +59,19 @@ impl FromStr for Input {
     type Err = Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let mut split = s.split("\n\n");
-        let shapes: Vec<Grid<char>> = split
-            .by_ref()
-            .take(6)
-            .map(|s| {
-                let (_, grid) = s.split_once("\n").unwrap();
-                Grid::<char>::from_str(grid).expect("Unable to parse grid")
-            })
-            .collect::<Vec<_>>();
+        let (shapes, regions) = s
+            .rsplit_once("\n\n")
+            .ok_or_else(|| Error::msg("Missing region section"))?;
+        let shapes: Vec<Grid<char>> = labeled_grids(shapes)?
+            .into_iter()
+            .map(|(_, grid)| grid)
+            .collect();
         let shape_areas = shapes
             .iter()
             .by_ref()
-            .map(|g| g.g.iter().filter(|&c| *c == '#').count())
+            .map(|g| g.iter().filter(|&c| *c == '#').count())
             .collect();
-        let regions = split
-            .last()
-            .unwrap()
+        let regions = regions
             .lines()
             .map(|s| {
                 let (region, required_presents) = s